@@ -0,0 +1,64 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use sleep_utils::parse_sleep_duration;
+
+const INPUTS: [&str; 5] = ["100", "250ms", "1.5s", "1m30s", "1h 2m 3s"];
+
+/// Repeatedly parse a handful of identical inputs, as a hot service loop would.
+///
+/// Run with `--features cache` to compare against the memoized parser, and against the
+/// cache-off baseline below.
+fn bench_repeated_inputs(c: &mut Criterion) {
+    c.bench_function("parse_sleep_duration/repeated", |b| {
+        b.iter(|| {
+            for input in INPUTS {
+                black_box(parse_sleep_duration(black_box(input)).unwrap());
+            }
+        })
+    });
+}
+
+/// Parse the same inputs as [`bench_repeated_inputs`] with the cache emptied before every
+/// call, so each one runs the full parser.
+///
+/// With the `cache` feature this is the baseline the memoized run is measured against.
+fn bench_repeated_inputs_uncached(c: &mut Criterion) {
+    c.bench_function("parse_sleep_duration/repeated_uncached", |b| {
+        b.iter(|| {
+            for input in INPUTS {
+                #[cfg(feature = "cache")]
+                sleep_utils::clear_parse_cache();
+                black_box(parse_sleep_duration(black_box(input)).unwrap());
+            }
+        })
+    });
+}
+
+/// Parse a stream of distinct inputs, so no result can come from the parse cache.
+///
+/// This runs the component tokenizer on every call.
+fn bench_distinct_inputs(c: &mut Criterion) {
+    let inputs: Vec<String> = (0..1000)
+        .map(|i| match i % 4 {
+            0 => format!("{}ms", i),
+            1 => format!("{}.5s", i),
+            2 => format!("{} minutes", i),
+            _ => format!("{}h{}m", i, i % 60),
+        })
+        .collect();
+
+    c.bench_function("parse_sleep_duration/distinct", |b| {
+        b.iter(|| {
+            for input in &inputs {
+                black_box(parse_sleep_duration(black_box(input)).unwrap());
+            }
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_repeated_inputs,
+    bench_repeated_inputs_uncached,
+    bench_distinct_inputs
+);
+criterion_main!(benches);
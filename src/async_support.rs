@@ -0,0 +1,209 @@
+use crate::smart_sleep::begin_sleep;
+use crate::zero_sleep_yields;
+use crate::{parse_sleep_duration, Result, SleepError, SleepInput};
+use std::future::Future;
+use std::panic::Location;
+use std::time::Duration;
+
+/// Async counterpart of [`smart_sleep`](crate::smart_sleep) for the Tokio runtime.
+///
+/// Accepts the same inputs and resolves them the same way, but awaits
+/// `tokio::time::sleep` instead of blocking the executor thread. Zero and negative
+/// inputs complete immediately, or yield to the runtime with
+/// [`set_zero_sleep_yields`](crate::set_zero_sleep_yields).
+///
+/// The input is resolved, recorded and reported to observers when this is called, not
+/// when the returned future is first polled.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if the input string cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use sleep_utils::smart_sleep_async;
+///
+/// smart_sleep_async("1ms").await.unwrap();
+/// smart_sleep_async(0).await.unwrap();
+/// # }
+/// ```
+#[track_caller]
+pub fn smart_sleep_async<S: Into<SleepInput>>(input: S) -> impl Future<Output = Result<()>> {
+    // Resolve eagerly so stats record the caller rather than this function
+    let duration = begin_sleep(&input.into(), Location::caller());
+    async move {
+        let duration = duration?;
+        if !duration.is_zero() {
+            tokio::time::sleep(duration).await;
+        } else if zero_sleep_yields() {
+            tokio::task::yield_now().await;
+        }
+        Ok(())
+    }
+}
+
+/// Awaits a future that computes the sleep input, then sleeps asynchronously for it.
+///
+/// This composes an asynchronous delay computation, such as reading a configured backoff
+/// from a remote service, with [`smart_sleep_async`].
+///
+/// # Errors
+///
+/// Returns the error produced by `fut`, or [`SleepError::InvalidDuration`] if the
+/// resolved input cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use sleep_utils::{smart_sleep_async_from, SleepInput};
+///
+/// smart_sleep_async_from(async { Ok(SleepInput::from("1ms")) }).await.unwrap();
+/// # }
+/// ```
+pub async fn smart_sleep_async_from<Fut>(fut: Fut) -> Result<()>
+where
+    Fut: std::future::Future<Output = Result<SleepInput>>,
+{
+    smart_sleep_async(fut.await?).await
+}
+
+/// Async counterpart of [`retry`](crate::retry), awaiting the backoff sleeps.
+///
+/// Runs `op` until its future resolves to `Ok`, sleeping with capped, jittered
+/// exponential backoff between failed attempts, and returns the first success or the
+/// last error once [`RetryConfig::max_attempts`](crate::RetryConfig::max_attempts) is
+/// exhausted. The delays are awaited with [`smart_sleep_async`], so the global time
+/// scale, sleep limit and observers apply; a rejected delay stops the retries early and
+/// returns the last error of `op`.
+///
+/// Requires both the `tokio` and `rand` features.
+#[cfg(feature = "rand")]
+pub async fn retry_async<T, E, Fut, F>(
+    mut op: F,
+    config: crate::RetryConfig,
+) -> std::result::Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = std::result::Result<T, E>>,
+{
+    let mut backoff = config.backoff();
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= config.max_attempts() => return Err(e),
+            Err(e) => {
+                let delay = config.jittered(backoff.next_delay());
+                if let Err(rejected) = smart_sleep_async(delay).await {
+                    crate::retry::log_rejected_delay(&rejected);
+                    return Err(e);
+                }
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Parse a duration intended for use as a timeout, e.g. with `tokio::time::timeout`.
+///
+/// This behaves like [`parse_sleep_duration`] but makes the intent clear at call sites
+/// that configure timeouts. Because a zero timeout fires immediately and is almost always
+/// a configuration mistake, zero (and negative) values are rejected.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if the input cannot be parsed or resolves to zero.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::parse_timeout;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_timeout("30s").unwrap(), Duration::from_secs(30));
+/// assert!(parse_timeout("0s").is_err());
+/// ```
+pub fn parse_timeout(input: &str) -> Result<Duration> {
+    let duration = parse_sleep_duration(input)?;
+    if duration.is_zero() {
+        return Err(SleepError::InvalidDuration(format!(
+            "Timeout must be greater than zero: '{}'",
+            input
+        )));
+    }
+    Ok(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timeout() -> Result<()> {
+        assert_eq!(parse_timeout("1m30s")?, Duration::from_secs(90));
+        assert_eq!(parse_timeout("250")?, Duration::from_millis(250));
+
+        assert!(matches!(
+            parse_timeout("0ms"),
+            Err(SleepError::InvalidDuration(_))
+        ));
+        assert!(parse_timeout("-5").is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_smart_sleep_async_from() -> Result<()> {
+        let start = tokio::time::Instant::now();
+        smart_sleep_async_from(async { Ok(SleepInput::from("50ms")) }).await?;
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        let failed = smart_sleep_async_from(async {
+            Err(SleepError::ParseError("lookup failed".to_string()))
+        })
+        .await;
+        assert!(matches!(failed, Err(SleepError::ParseError(_))));
+        Ok(())
+    }
+
+    #[cfg(feature = "rand")]
+    #[tokio::test]
+    async fn test_retry_async_succeeds_after_retries() -> Result<()> {
+        use std::cell::Cell;
+
+        let config = crate::RetryConfig::new("5ms")?
+            .with_max_attempts(4)?
+            .with_jitter(0.1)?;
+
+        let calls = Cell::new(0);
+        let start = tokio::time::Instant::now();
+        let result: std::result::Result<u32, &str> = retry_async(
+            || {
+                calls.set(calls.get() + 1);
+                let attempt = calls.get();
+                async move {
+                    if attempt < 3 {
+                        Err("not yet")
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            },
+            config,
+        )
+        .await;
+
+        assert_eq!(result, Ok(3));
+        // Two backoff sleeps of nominally 5ms and 10ms, jittered by up to 10%
+        assert!(start.elapsed() >= Duration::from_micros(13_500));
+
+        let failing: std::result::Result<(), &str> =
+            retry_async(|| async { Err("always") }, crate::RetryConfig::new("1ms")?).await;
+        assert_eq!(failing, Err("always"));
+        Ok(())
+    }
+}
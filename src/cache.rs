@@ -0,0 +1,86 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Maximum number of distinct inputs kept in the parse cache
+const CAPACITY: usize = 128;
+
+lazy_static! {
+    static ref PARSE_CACHE: Mutex<LruCache> = Mutex::new(LruCache::default());
+}
+
+/// A small least-recently-used map from input strings to parsed durations
+#[derive(Default)]
+struct LruCache {
+    entries: HashMap<String, (Duration, u64)>,
+    tick: u64,
+}
+
+impl LruCache {
+    fn get(&mut self, key: &str) -> Option<Duration> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(key).map(|(duration, used)| {
+            *used = tick;
+            *duration
+        })
+    }
+
+    fn insert(&mut self, key: &str, duration: Duration) {
+        if self.entries.len() >= CAPACITY && !self.entries.contains_key(key) {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.tick += 1;
+        self.entries.insert(key.to_string(), (duration, self.tick));
+    }
+}
+
+/// Look up a previously parsed input
+pub(crate) fn get(input: &str) -> Option<Duration> {
+    PARSE_CACHE.lock().ok()?.get(input)
+}
+
+/// Remember a successfully parsed input
+pub(crate) fn insert(input: &str, duration: Duration) {
+    if let Ok(mut cache) = PARSE_CACHE.lock() {
+        cache.insert(input, duration);
+    }
+}
+
+/// Clears the parse cache used by [`parse_sleep_duration`](crate::parse_sleep_duration).
+///
+/// Only available with the `cache` feature.
+pub fn clear_parse_cache() {
+    if let Ok(mut cache) = PARSE_CACHE.lock() {
+        cache.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut cache = LruCache::default();
+        for i in 0..CAPACITY {
+            cache.insert(&i.to_string(), Duration::from_millis(i as u64));
+        }
+        // Touch the oldest entry so the second one is evicted instead
+        assert_eq!(cache.get("0"), Some(Duration::ZERO));
+        cache.insert("new", Duration::from_secs(1));
+
+        assert_eq!(cache.entries.len(), CAPACITY);
+        assert_eq!(cache.get("0"), Some(Duration::ZERO));
+        assert_eq!(cache.get("1"), None);
+        assert_eq!(cache.get("new"), Some(Duration::from_secs(1)));
+    }
+}
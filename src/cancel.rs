@@ -0,0 +1,128 @@
+use crate::smart_sleep::run_sleep;
+use crate::{Result, SleepInput};
+use std::panic::Location;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often [`smart_sleep_cancellable`] checks its cancellation flag
+pub const DEFAULT_CANCEL_POLL: Duration = Duration::from_millis(10);
+
+/// How a cancellable sleep ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SleepOutcome {
+    /// The full duration elapsed
+    Completed,
+    /// The cancellation flag was set before the duration elapsed
+    Cancelled,
+}
+
+/// Sleeps for the input unless `cancel` is set first.
+///
+/// The sleep is split into slices of [`DEFAULT_CANCEL_POLL`] and the flag is checked
+/// before each one, so cancellation is noticed within about 10ms. A flag that is already
+/// set returns [`SleepOutcome::Cancelled`] without sleeping. Use
+/// [`smart_sleep_cancellable_with`] to pick a different polling granularity.
+///
+/// Inputs are resolved exactly like [`smart_sleep`](crate::smart_sleep), including the
+/// global time scale and granularity.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`](crate::SleepError::InvalidDuration) if the
+/// input string cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{smart_sleep_cancellable, SleepOutcome};
+/// use std::sync::atomic::AtomicBool;
+///
+/// let cancel = AtomicBool::new(false);
+/// assert_eq!(smart_sleep_cancellable("5ms", &cancel).unwrap(), SleepOutcome::Completed);
+///
+/// let cancel = AtomicBool::new(true);
+/// assert_eq!(smart_sleep_cancellable("10m", &cancel).unwrap(), SleepOutcome::Cancelled);
+/// ```
+#[track_caller]
+pub fn smart_sleep_cancellable<S: Into<SleepInput>>(
+    input: S,
+    cancel: &AtomicBool,
+) -> Result<SleepOutcome> {
+    smart_sleep_cancellable_with(input, cancel, DEFAULT_CANCEL_POLL)
+}
+
+/// Like [`smart_sleep_cancellable`], but checks the flag every `poll` instead.
+///
+/// A smaller `poll` reacts to cancellation sooner at the cost of more wakeups; a zero
+/// `poll` checks the flag continuously.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`](crate::SleepError::InvalidDuration) if the
+/// input string cannot be parsed.
+#[track_caller]
+pub fn smart_sleep_cancellable_with<S: Into<SleepInput>>(
+    input: S,
+    cancel: &AtomicBool,
+    poll: Duration,
+) -> Result<SleepOutcome> {
+    let mut outcome = None;
+    run_sleep(&input.into(), Location::caller(), |duration| {
+        outcome = Some(wait_cancellable(duration, cancel, poll));
+    })?;
+    // Skipped sleeps still honor a flag that is already set
+    Ok(outcome.unwrap_or_else(|| wait_cancellable(Duration::ZERO, cancel, poll)))
+}
+
+/// Sleep for `duration` in slices of `poll`, checking `cancel` before each one
+fn wait_cancellable(duration: Duration, cancel: &AtomicBool, poll: Duration) -> SleepOutcome {
+    let start = Instant::now();
+    loop {
+        if cancel.load(Ordering::Acquire) {
+            return SleepOutcome::Cancelled;
+        }
+        let remaining = duration.saturating_sub(start.elapsed());
+        if remaining.is_zero() {
+            return SleepOutcome::Completed;
+        }
+        std::thread::sleep(remaining.min(poll));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_cancellable_sleep_cancelled() -> Result<()> {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&cancel);
+        let worker = std::thread::spawn(move || {
+            let start = Instant::now();
+            let outcome = smart_sleep_cancellable("10s", &flag);
+            (outcome, start.elapsed())
+        });
+
+        std::thread::sleep(Duration::from_millis(50));
+        cancel.store(true, Ordering::Release);
+
+        let (outcome, elapsed) = worker.join().unwrap();
+        assert_eq!(outcome?, SleepOutcome::Cancelled);
+        assert!(elapsed >= Duration::from_millis(50));
+        assert!(elapsed < Duration::from_secs(1), "{:?}", elapsed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cancellable_sleep_completes() -> Result<()> {
+        let cancel = AtomicBool::new(false);
+        let start = Instant::now();
+        let outcome = smart_sleep_cancellable_with("30ms", &cancel, Duration::from_millis(7))?;
+        assert_eq!(outcome, SleepOutcome::Completed);
+        assert!(start.elapsed() >= Duration::from_millis(30));
+
+        assert!(smart_sleep_cancellable("bogus", &cancel).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{smart_sleep, Result, SleepError, SleepInput};
+use std::time::Duration;
+
+/// What [`smart_sleep_capped_with`] does when the input exceeds the cap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CapMode {
+    /// Sleep for the cap instead
+    #[default]
+    Clamp,
+    /// Return [`SleepError::NumberOutOfRange`] without sleeping
+    Error,
+}
+
+/// Sleeps for the input, but never longer than `max`.
+///
+/// Guards against runaway sleeps from bad configuration: a typo like `"100h"` sleeps for
+/// `max` instead of days. Returns the duration that was slept for, before the global time
+/// scale. Use [`smart_sleep_capped_with`] to reject oversized inputs instead.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if the input or the cap cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::smart_sleep_capped;
+/// use std::time::Duration;
+///
+/// assert_eq!(smart_sleep_capped("100h", "5ms").unwrap(), Duration::from_millis(5));
+/// assert_eq!(smart_sleep_capped("1ms", "5ms").unwrap(), Duration::from_millis(1));
+/// ```
+#[track_caller]
+pub fn smart_sleep_capped<S, M>(input: S, max: M) -> Result<Duration>
+where
+    S: Into<SleepInput>,
+    M: Into<SleepInput>,
+{
+    smart_sleep_capped_with(input, max, CapMode::Clamp)
+}
+
+/// Like [`smart_sleep_capped`], with `mode` choosing how an oversized input is handled.
+///
+/// An input exactly equal to the cap is not oversized.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if the input or the cap cannot be parsed, and
+/// [`SleepError::NumberOutOfRange`] if the input exceeds the cap in [`CapMode::Error`].
+#[track_caller]
+pub fn smart_sleep_capped_with<S, M>(input: S, max: M, mode: CapMode) -> Result<Duration>
+where
+    S: Into<SleepInput>,
+    M: Into<SleepInput>,
+{
+    let requested = input.into().to_duration()?;
+    let max = max.into().to_duration()?;
+
+    let duration = if requested <= max {
+        requested
+    } else {
+        match mode {
+            CapMode::Clamp => max,
+            CapMode::Error => {
+                return Err(SleepError::NumberOutOfRange(format!(
+                    "Requested sleep of {:?} exceeds the cap of {:?}",
+                    requested, max
+                )))
+            }
+        }
+    };
+
+    smart_sleep(duration)?;
+    Ok(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_capped_clamp_mode() -> Result<()> {
+        assert_eq!(smart_sleep_capped("2ms", "10ms")?, Duration::from_millis(2));
+        assert_eq!(smart_sleep_capped(10, "10ms")?, Duration::from_millis(10));
+
+        let start = Instant::now();
+        assert_eq!(
+            smart_sleep_capped("100h", "10ms")?,
+            Duration::from_millis(10)
+        );
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        assert!(smart_sleep_capped("bogus", "10ms").is_err());
+        assert!(smart_sleep_capped("1ms", "bogus").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_capped_error_mode() -> Result<()> {
+        let mode = CapMode::Error;
+        assert_eq!(
+            smart_sleep_capped_with("2ms", "10ms", mode)?,
+            Duration::from_millis(2)
+        );
+        assert_eq!(
+            smart_sleep_capped_with("10ms", 10, mode)?,
+            Duration::from_millis(10)
+        );
+
+        let start = Instant::now();
+        let err = smart_sleep_capped_with("100h", "10ms", mode).unwrap_err();
+        assert!(matches!(err, SleepError::NumberOutOfRange(_)));
+        assert!(start.elapsed() < Duration::from_millis(10));
+        Ok(())
+    }
+}
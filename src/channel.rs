@@ -0,0 +1,78 @@
+use crate::{Result, SleepInput};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+
+/// Waits for a message on `rx` for at most the duration described by `input`.
+///
+/// This resolves `input` like [`smart_sleep`](crate::smart_sleep) does and then calls
+/// [`Receiver::recv_timeout`], so worker loops can pass `"500ms"` directly instead of
+/// building a [`Duration`](std::time::Duration). Zero and negative inputs poll the
+/// channel without waiting.
+///
+/// The outer [`Result`] reports input parsing errors; the inner one is the outcome of
+/// the receive.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`](crate::SleepError::InvalidDuration) if `input`
+/// cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::recv_timeout_smart;
+/// use std::sync::mpsc::{channel, RecvTimeoutError};
+///
+/// let (tx, rx) = channel();
+/// assert_eq!(recv_timeout_smart(&rx, "1ms").unwrap(), Err(RecvTimeoutError::Timeout));
+///
+/// tx.send(42).unwrap();
+/// assert_eq!(recv_timeout_smart(&rx, "1s").unwrap(), Ok(42));
+/// ```
+pub fn recv_timeout_smart<T, S: Into<SleepInput>>(
+    rx: &Receiver<T>,
+    input: S,
+) -> Result<std::result::Result<T, RecvTimeoutError>> {
+    let timeout = input.into().to_duration()?;
+    Ok(rx.recv_timeout(timeout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_recv_timeout_smart_times_out() -> Result<()> {
+        let (_tx, rx) = channel::<()>();
+        let start = Instant::now();
+        assert_eq!(
+            recv_timeout_smart(&rx, "20ms")?,
+            Err(RecvTimeoutError::Timeout)
+        );
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        assert!(recv_timeout_smart(&rx, "bogus").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_recv_timeout_smart_receives() -> Result<()> {
+        let (tx, rx) = channel();
+        let sender = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(10));
+            tx.send("done").unwrap();
+        });
+
+        let start = Instant::now();
+        assert_eq!(recv_timeout_smart(&rx, "5s")?, Ok("done"));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        sender.join().unwrap();
+
+        assert_eq!(
+            recv_timeout_smart(&rx, 0)?,
+            Err(RecvTimeoutError::Disconnected)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,270 @@
+use crate::core_parse::duration_from_nanos;
+use crate::{Result, SleepError, Unit};
+use lazy_static::lazy_static;
+use std::sync::RwLock;
+use std::time::Duration;
+
+lazy_static! {
+    static ref SLEEP_GRANULARITY: RwLock<Duration> = RwLock::new(Duration::ZERO);
+    static ref DEFAULT_UNIT: RwLock<Unit> = RwLock::new(Unit::Millis);
+    static ref TIME_SCALE: RwLock<f64> = RwLock::new(env_time_scale());
+    static ref TICK_DURATION: RwLock<Option<Duration>> = RwLock::new(None);
+    static ref ZERO_SLEEP_YIELDS: RwLock<bool> = RwLock::new(false);
+    static ref MAX_SLEEP: RwLock<Option<Duration>> = RwLock::new(None);
+}
+
+/// Sets an upper limit for any single sleep, or removes it with `None`.
+///
+/// When a limit is set, [`smart_sleep`](crate::smart_sleep) and the other sleeping
+/// functions refuse resolved durations above it with [`SleepError::NumberOutOfRange`]
+/// instead of blocking. Unlike clamping, the caller finds out that the request was too
+/// long. The limit is compared with the duration after time scale and granularity.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{set_max_sleep, smart_sleep};
+/// use std::time::Duration;
+///
+/// set_max_sleep(Some(Duration::from_millis(10)));
+/// assert!(smart_sleep("1h").is_err());
+/// smart_sleep("1ms").unwrap();
+///
+/// set_max_sleep(None);
+/// ```
+pub fn set_max_sleep(limit: Option<Duration>) {
+    *MAX_SLEEP.write().unwrap_or_else(|e| e.into_inner()) = limit;
+}
+
+/// Returns the configured sleep limit, see [`set_max_sleep`].
+pub fn max_sleep() -> Option<Duration> {
+    *MAX_SLEEP.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Reject a resolved duration above the configured sleep limit
+pub(crate) fn check_max_sleep(duration: Duration) -> Result<Duration> {
+    match max_sleep() {
+        Some(limit) if duration > limit => Err(SleepError::NumberOutOfRange(format!(
+            "Requested sleep of {:?} exceeds the maximum of {:?}",
+            duration, limit
+        ))),
+        _ => Ok(duration),
+    }
+}
+
+/// Makes [`smart_sleep`](crate::smart_sleep) yield the thread for zero-length sleeps.
+///
+/// By default a zero or negative input returns immediately. When enabled, such calls run
+/// [`std::thread::yield_now`] instead, which keeps spin loops built on `smart_sleep(0)`
+/// cooperative with other threads.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{set_zero_sleep_yields, smart_sleep, zero_sleep_yields};
+///
+/// set_zero_sleep_yields(true);
+/// assert!(zero_sleep_yields());
+/// smart_sleep(0).unwrap(); // yields instead of returning at once
+///
+/// set_zero_sleep_yields(false);
+/// ```
+pub fn set_zero_sleep_yields(enabled: bool) {
+    *ZERO_SLEEP_YIELDS.write().unwrap_or_else(|e| e.into_inner()) = enabled;
+}
+
+/// Returns whether zero-length sleeps yield the thread, see [`set_zero_sleep_yields`].
+pub fn zero_sleep_yields() -> bool {
+    *ZERO_SLEEP_YIELDS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Sets the duration of one tick for the `"t"`, `"tick"` and `"ticks"` units.
+///
+/// Game loops and simulations can configure their tick once and then express delays in
+/// ticks: with a 16ms tick, `"3ticks"` parses as 48ms. Passing `None` removes the
+/// configuration, after which tick units are rejected by the parser.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{parse_sleep_duration, set_tick_duration};
+/// use std::time::Duration;
+///
+/// set_tick_duration(Some(Duration::from_millis(16)));
+/// assert_eq!(parse_sleep_duration("3ticks").unwrap(), Duration::from_millis(48));
+///
+/// set_tick_duration(None);
+/// assert!(parse_sleep_duration("3ticks").is_err());
+/// ```
+pub fn set_tick_duration(tick: Option<Duration>) {
+    *TICK_DURATION.write().unwrap_or_else(|e| e.into_inner()) = tick;
+    #[cfg(feature = "cache")]
+    crate::cache::clear_parse_cache();
+}
+
+/// Returns the configured tick duration, see [`set_tick_duration`].
+pub fn tick_duration() -> Option<Duration> {
+    *TICK_DURATION.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Environment variable holding the initial global time scale
+pub const SCALE_ENV_VAR: &str = "SLEEP_UTILS_SCALE";
+
+/// Read the initial time scale from the environment, defaulting to 1.0
+fn env_time_scale() -> f64 {
+    std::env::var(SCALE_ENV_VAR)
+        .ok()
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|scale| scale.is_finite() && *scale >= 0.0)
+        .unwrap_or(1.0)
+}
+
+/// Sets the global factor applied to every [`smart_sleep`](crate::smart_sleep) duration.
+///
+/// A scale of `0.5` halves all sleeps and `2.0` doubles them. The initial value is read
+/// once from the `SLEEP_UTILS_SCALE` environment variable (defaulting to 1.0), so CI can
+/// speed up or slow down all sleeps without code changes; calling this function
+/// overrides the environment value.
+///
+/// # Errors
+///
+/// Returns [`SleepError::NumberOutOfRange`] if `scale` is negative or not finite.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{set_time_scale, time_scale};
+///
+/// set_time_scale(0.5).unwrap();
+/// assert_eq!(time_scale(), 0.5);
+/// assert!(set_time_scale(-1.0).is_err());
+///
+/// set_time_scale(1.0).unwrap();
+/// ```
+pub fn set_time_scale(scale: f64) -> Result<()> {
+    if !(scale.is_finite() && scale >= 0.0) {
+        return Err(SleepError::NumberOutOfRange(format!(
+            "Time scale must be a finite, non-negative number, got {}",
+            scale
+        )));
+    }
+    *TIME_SCALE.write().unwrap_or_else(|e| e.into_inner()) = scale;
+    Ok(())
+}
+
+/// Returns the global time scale, see [`set_time_scale`].
+pub fn time_scale() -> f64 {
+    *TIME_SCALE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Multiply a duration by the global time scale, saturating on overflow
+pub(crate) fn apply_time_scale(duration: Duration) -> Duration {
+    let scale = time_scale();
+    if scale == 1.0 {
+        return duration;
+    }
+    Duration::try_from_secs_f64(duration.as_secs_f64() * scale).unwrap_or(Duration::MAX)
+}
+
+/// Sets the unit used for unit-less text input such as `"2"` or `"1.5"`.
+///
+/// The default is [`Unit::Millis`]. Integer and float strings both honor this setting, so
+/// with seconds configured `"2"` parses as 2s and `"1.5"` as 1500ms. Numeric
+/// [`SleepInput::Number`](crate::SleepInput::Number) values are always milliseconds.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{parse_sleep_duration, set_default_unit, Unit};
+/// use std::time::Duration;
+///
+/// set_default_unit(Unit::Seconds);
+/// assert_eq!(parse_sleep_duration("1.5").unwrap(), Duration::from_millis(1500));
+///
+/// set_default_unit(Unit::Millis);
+/// ```
+pub fn set_default_unit(unit: Unit) {
+    *DEFAULT_UNIT.write().unwrap_or_else(|e| e.into_inner()) = unit;
+    #[cfg(feature = "cache")]
+    crate::cache::clear_parse_cache();
+}
+
+/// Returns the unit used for unit-less text input, see [`set_default_unit`].
+pub fn default_unit() -> Unit {
+    *DEFAULT_UNIT.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Sets the global sleep granularity used by [`smart_sleep`](crate::smart_sleep).
+///
+/// When the granularity is non-zero, every resolved sleep is rounded up to the next
+/// multiple of it so that timers coalesce and the thread wakes up less often. This
+/// trades precision for fewer wakeups. A granularity of [`Duration::ZERO`] (the
+/// default) disables rounding.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{set_sleep_granularity, sleep_granularity};
+/// use std::time::Duration;
+///
+/// set_sleep_granularity(Duration::from_millis(10));
+/// assert_eq!(sleep_granularity(), Duration::from_millis(10));
+///
+/// set_sleep_granularity(Duration::ZERO);
+/// ```
+pub fn set_sleep_granularity(granularity: Duration) {
+    *SLEEP_GRANULARITY.write().unwrap_or_else(|e| e.into_inner()) = granularity;
+}
+
+/// Returns the global sleep granularity set by [`set_sleep_granularity`].
+pub fn sleep_granularity() -> Duration {
+    *SLEEP_GRANULARITY.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Round a duration up to the next multiple of the configured granularity
+pub(crate) fn apply_granularity(duration: Duration) -> Duration {
+    let granularity = sleep_granularity().as_nanos();
+    if granularity == 0 || duration.is_zero() {
+        return duration;
+    }
+
+    let nanos = duration.as_nanos();
+    duration_from_nanos(nanos.div_ceil(granularity).saturating_mul(granularity))
+}
+
+/// Restores every piece of global state to its default.
+///
+/// This resets the time scale to 1.0 (ignoring `SLEEP_UTILS_SCALE`), the sleep
+/// granularity to zero, the default unit to milliseconds, removes the tick duration and
+/// sleep limit, turns off zero-sleep yielding and removes all sleep observers and named
+/// references.
+/// With the `stats` and `cache` features it also resets the sleep statistics and empties
+/// the parse cache.
+///
+/// Globals are shared by the whole process, so test harnesses should call this in their
+/// setup while holding a lock that serializes the tests touching global state.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{reset_all_globals, set_time_scale, time_scale};
+///
+/// set_time_scale(0.1).unwrap();
+/// reset_all_globals();
+/// assert_eq!(time_scale(), 1.0);
+/// ```
+pub fn reset_all_globals() {
+    *TIME_SCALE.write().unwrap_or_else(|e| e.into_inner()) = 1.0;
+    set_sleep_granularity(Duration::ZERO);
+    set_default_unit(Unit::Millis);
+    set_tick_duration(None);
+    set_zero_sleep_yields(false);
+    set_max_sleep(None);
+    crate::observers::clear_sleep_observers();
+    crate::references::clear_references();
+
+    #[cfg(feature = "stats")]
+    crate::stats::reset_sleep_stats();
+    #[cfg(feature = "cache")]
+    crate::cache::clear_parse_cache();
+}
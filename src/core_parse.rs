@@ -0,0 +1,714 @@
+use crate::SleepError;
+use core::fmt;
+use core::time::Duration;
+
+/// Nanoseconds per millisecond, the unit of bare numbers
+const NANOS_PER_MILLI: u128 = 1_000_000;
+
+/// Exponents are clamped to this magnitude; anything larger over- or underflows anyway
+const MAX_EXPONENT: i32 = 1_000;
+
+/// Every unit spelling the parser accepts, with its multiplier in nanoseconds
+pub(crate) const UNIT_ALIASES: &[(&str, u64)] = &[
+    ("ns", 1),
+    ("nano", 1),
+    ("nanos", 1),
+    ("nanosecond", 1),
+    ("nanoseconds", 1),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("μs", 1_000),
+    ("micro", 1_000),
+    ("micros", 1_000),
+    ("microsecond", 1_000),
+    ("microseconds", 1_000),
+    ("ms", 1_000_000),
+    ("milli", 1_000_000),
+    ("millis", 1_000_000),
+    ("millisec", 1_000_000),
+    ("millisecs", 1_000_000),
+    ("millisecond", 1_000_000),
+    ("milliseconds", 1_000_000),
+    ("s", 1_000_000_000),
+    ("sec", 1_000_000_000),
+    ("secs", 1_000_000_000),
+    ("second", 1_000_000_000),
+    ("seconds", 1_000_000_000),
+    ("m", 60_000_000_000),
+    ("min", 60_000_000_000),
+    ("mins", 60_000_000_000),
+    ("minute", 60_000_000_000),
+    ("minutes", 60_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("hr", 3_600_000_000_000),
+    ("hrs", 3_600_000_000_000),
+    ("hour", 3_600_000_000_000),
+    ("hours", 3_600_000_000_000),
+    ("d", 86_400_000_000_000),
+    ("day", 86_400_000_000_000),
+    ("days", 86_400_000_000_000),
+    ("w", 604_800_000_000_000),
+    ("week", 604_800_000_000_000),
+    ("weeks", 604_800_000_000_000),
+];
+
+/// Why [`parse_duration_core`] rejected its input.
+///
+/// This error carries no heap data so it works without `alloc`. Spans are byte ranges
+/// into the input. It converts into [`SleepError`] for callers that use the rest of the
+/// crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoreParseError {
+    /// The input holds no duration components
+    Empty,
+    /// Text that belongs to no component, such as a stray word, a number without a unit
+    /// or a misplaced digit separator
+    UnknownToken {
+        /// Byte offset where the text starts
+        start: usize,
+        /// Byte offset just past the text
+        end: usize,
+    },
+    /// A component whose unit is not recognized
+    UnknownUnit {
+        /// Byte offset where the unit starts
+        start: usize,
+        /// Byte offset just past the unit
+        end: usize,
+    },
+    /// A component, or the total, does not fit in a [`Duration`]
+    Overflow {
+        /// Byte offset where the offending component starts
+        start: usize,
+        /// Byte offset just past the offending component
+        end: usize,
+    },
+}
+
+impl fmt::Display for CoreParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoreParseError::Empty => f.write_str("empty duration input"),
+            CoreParseError::UnknownToken { start, end } => {
+                write!(f, "unrecognized text at bytes {}..{}", start, end)
+            }
+            CoreParseError::UnknownUnit { start, end } => {
+                write!(f, "unknown unit at bytes {}..{}", start, end)
+            }
+            CoreParseError::Overflow { start, end } => {
+                write!(f, "duration at bytes {}..{} is too large", start, end)
+            }
+        }
+    }
+}
+
+impl From<CoreParseError> for SleepError {
+    fn from(error: CoreParseError) -> Self {
+        use alloc::string::ToString;
+
+        match error {
+            CoreParseError::Empty => SleepError::Empty,
+            CoreParseError::Overflow { .. } => SleepError::NumberOutOfRange(error.to_string()),
+            _ => SleepError::InvalidDuration(error.to_string()),
+        }
+    }
+}
+
+/// Parse a duration without `std`, regular expressions or allocation.
+///
+/// This is the grammar behind [`parse_sleep_duration`](crate::parse_sleep_duration):
+/// one or more `number unit` components such as `"1h 30m"`, `"1.5s"`, `"1e3ms"` or
+/// `"1,500 millis"`, separated by whitespace, commas or the word "and", using the same
+/// unit spellings. Numbers may use `_` and `,` digit grouping, a fraction and an
+/// exponent, and are exact down to the nanosecond. A lone number is milliseconds, and a
+/// lone negative number is zero.
+///
+/// The forms that need `std` are left to `parse_sleep_duration`: the configurable default
+/// unit, ticks, the `~` marker, clock and ISO 8601 formats, and treating separator-only
+/// input as zero. Units match ASCII case-insensitively.
+///
+/// # Errors
+///
+/// Returns a [`CoreParseError`] describing the first problem found.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{parse_duration_core, CoreParseError};
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_duration_core("1m and 30.5s"), Ok(Duration::from_millis(90_500)));
+/// assert_eq!(parse_duration_core("250"), Ok(Duration::from_millis(250)));
+/// assert_eq!(parse_duration_core("-5"), Ok(Duration::ZERO));
+/// assert_eq!(
+///     parse_duration_core("5 bananas"),
+///     Err(CoreParseError::UnknownUnit { start: 2, end: 9 })
+/// );
+/// ```
+pub fn parse_duration_core(input: &str) -> Result<Duration, CoreParseError> {
+    let nanos = sum_components(input, NANOS_PER_MILLI, |unit| {
+        UNIT_ALIASES
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(unit))
+            .map(|(_, multiplier)| u128::from(*multiplier))
+    })
+    .map_err(|rejection| rejection.error)?;
+    Ok(duration_from_nanos(nanos))
+}
+
+/// Why [`sum_components`] rejected its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Rejection {
+    /// The first problem found
+    pub(crate) error: CoreParseError,
+    /// Whether any component with a known unit was found
+    pub(crate) recognized: bool,
+}
+
+/// Sum the components of a duration string into nanoseconds
+///
+/// A lone number, optionally signed, is worth `bare` nanoseconds per unit, and zero if
+/// negative. Otherwise every token must be a component whose unit `resolve` maps to its
+/// nanoseconds. The total is at most [`Duration::MAX`].
+pub(crate) fn sum_components(
+    input: &str,
+    bare: u128,
+    mut resolve: impl FnMut(&str) -> Option<u128>,
+) -> Result<u128, Rejection> {
+    let reject = |error, recognized| Rejection { error, recognized };
+    let whole = CoreParseError::Overflow {
+        start: 0,
+        end: input.len(),
+    };
+
+    if let Some((negative, number)) = bare_number(input) {
+        if negative {
+            return Ok(0);
+        }
+        return number
+            .to_nanos(bare)
+            .filter(|nanos| *nanos <= Duration::MAX.as_nanos())
+            .ok_or(reject(whole, false));
+    }
+
+    let mut total: u128 = 0;
+    let mut recognized = false;
+    let mut problem = None;
+    for token in Tokens::new(input) {
+        let component = match token {
+            Token::Component(component) => component,
+            Token::Stray { start, end } => {
+                problem = problem.or(Some(CoreParseError::UnknownToken { start, end }));
+                continue;
+            }
+        };
+        let (start, end) = (component.start, component.end);
+        if component.unit.is_empty() {
+            problem = problem.or(Some(CoreParseError::UnknownToken { start, end }));
+            continue;
+        }
+        let multiplier = match resolve(component.unit) {
+            Some(multiplier) => multiplier,
+            None => {
+                problem = problem.or(Some(CoreParseError::UnknownUnit {
+                    start: component.unit_start,
+                    end,
+                }));
+                continue;
+            }
+        };
+        total = component
+            .number
+            .to_nanos(multiplier)
+            .and_then(|nanos| total.checked_add(nanos))
+            .filter(|total| *total <= Duration::MAX.as_nanos())
+            .ok_or(reject(CoreParseError::Overflow { start, end }, recognized))?;
+        recognized = true;
+    }
+
+    match problem {
+        Some(error) => Err(reject(error, recognized)),
+        None if !recognized => Err(reject(CoreParseError::Empty, false)),
+        None => Ok(total),
+    }
+}
+
+/// Read the input as a lone number with an optional sign, returning `(negative, number)`
+fn bare_number(input: &str) -> Option<(bool, Number)> {
+    let body = input.trim();
+    let (negative, digits) = match body.as_bytes().first() {
+        Some(b'-') => (true, &body[1..]),
+        Some(b'+') => (false, &body[1..]),
+        _ => (false, body),
+    };
+    let mut tokens = Tokens::new(digits);
+    match (tokens.next(), tokens.next()) {
+        (Some(Token::Component(component)), None)
+            if component.unit.is_empty() && component.start == 0 =>
+        {
+            Some((negative, component.number))
+        }
+        _ => None,
+    }
+}
+
+/// Build a duration from a nanosecond count that may exceed `u64`
+///
+/// Whole seconds saturate at `u64::MAX`, so counts past [`Duration::MAX`] do not wrap.
+pub(crate) fn duration_from_nanos(nanos: u128) -> Duration {
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+    let secs = (nanos / NANOS_PER_SEC).min(u128::from(u64::MAX)) as u64;
+    Duration::new(secs, (nanos % NANOS_PER_SEC) as u32)
+}
+
+/// A decimal number exactly as written, worth `mantissa * 10^exponent`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Number {
+    mantissa: u128,
+    exponent: i32,
+}
+
+impl Number {
+    /// Append a digit, dropping it if the mantissa is full
+    ///
+    /// A dropped whole digit still scales the value, while a dropped fraction digit is
+    /// far below a nanosecond for every unit.
+    fn push_digit(&mut self, digit: u8, fraction: bool) {
+        match self
+            .mantissa
+            .checked_mul(10)
+            .and_then(|mantissa| mantissa.checked_add(u128::from(digit)))
+        {
+            Some(mantissa) => {
+                self.mantissa = mantissa;
+                if fraction {
+                    self.exponent = self.exponent.saturating_sub(1);
+                }
+            }
+            None if !fraction => self.exponent = self.exponent.saturating_add(1),
+            None => {}
+        }
+    }
+
+    /// The value times `multiplier`, truncated to an integer, or `None` on overflow
+    pub(crate) fn to_nanos(self, multiplier: u128) -> Option<u128> {
+        let (mut mantissa, mut exponent) = (self.mantissa, self.exponent);
+        if mantissa == 0 || multiplier == 0 {
+            return Some(0);
+        }
+        if exponent >= 0 {
+            return mantissa
+                .checked_mul(multiplier)?
+                .checked_mul(10u128.checked_pow(exponent.unsigned_abs())?);
+        }
+
+        // Drop fraction digits until the product fits; they are below the result's precision
+        let product = loop {
+            if let Some(product) = mantissa.checked_mul(multiplier) {
+                break product;
+            }
+            if exponent == 0 {
+                return None;
+            }
+            mantissa /= 10;
+            exponent += 1;
+        };
+        Some(match 10u128.checked_pow(exponent.unsigned_abs()) {
+            Some(divisor) => product / divisor,
+            None => 0,
+        })
+    }
+
+    /// Read `text` as a single number in the component grammar, such as `"1.5"` or `"1e3"`
+    #[cfg(feature = "std")]
+    pub(crate) fn parse(text: &str) -> Option<Self> {
+        match Tokens::new(text).next()? {
+            Token::Component(component)
+                if component.unit.is_empty()
+                    && (component.start, component.end) == (0, text.len()) =>
+            {
+                Some(component.number)
+            }
+            _ => None,
+        }
+    }
+
+    /// The value as the nearest `f64`
+    #[cfg(feature = "std")]
+    pub(crate) fn to_f64(self) -> f64 {
+        let scale = 10f64.powi(self.exponent.abs());
+        if self.exponent < 0 {
+            self.mantissa as f64 / scale
+        } else {
+            self.mantissa as f64 * scale
+        }
+    }
+
+    /// The value as an integer, if it has no fractional part
+    #[cfg(any(test, feature = "std"))]
+    pub(crate) fn to_integer(self) -> Option<u128> {
+        if self.exponent >= 0 {
+            return self
+                .mantissa
+                .checked_mul(10u128.checked_pow(self.exponent.unsigned_abs())?);
+        }
+        let divisor = 10u128.checked_pow(self.exponent.unsigned_abs())?;
+        self.mantissa
+            .is_multiple_of(divisor)
+            .then_some(self.mantissa / divisor)
+    }
+}
+
+/// A `number unit` component found by [`Tokens`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Component<'a> {
+    /// The number, with digit separators removed
+    pub(crate) number: Number,
+    /// The unit word exactly as written, empty if the number has no unit
+    pub(crate) unit: &'a str,
+    /// Byte offset of the number
+    pub(crate) start: usize,
+    /// Byte offset of the unit, or of the end of the number if there is none
+    pub(crate) unit_start: usize,
+    /// Byte offset just past the component
+    pub(crate) end: usize,
+}
+
+/// A token of a duration string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Token<'a> {
+    /// A number and the unit that follows it, if any
+    Component(Component<'a>),
+    /// Text that is neither a component nor a separator
+    Stray {
+        /// Byte offset where the text starts
+        start: usize,
+        /// Byte offset just past the text
+        end: usize,
+    },
+}
+
+/// Splits a duration string into components and stray text, skipping separators
+///
+/// Separators are whitespace, single commas and the word "and"; a second comma in a row
+/// is stray. A component is a number with an optional fraction and `e` exponent,
+/// optionally followed by whitespace and a unit word. Inside the number, `_` may separate
+/// digits and `,` may start a group of exactly three whole digits; any other `_`, or a
+/// `,` between digits, is stray on its own. Other stray text runs up to the next
+/// separator or number.
+#[derive(Debug, Clone)]
+pub(crate) struct Tokens<'a> {
+    input: &'a str,
+    at: usize,
+}
+
+impl<'a> Tokens<'a> {
+    /// Tokenize `input` from the start
+    pub(crate) fn new(input: &'a str) -> Self {
+        Tokens { input, at: 0 }
+    }
+
+    fn byte(&self, at: usize) -> Option<u8> {
+        self.input.as_bytes().get(at).copied()
+    }
+
+    fn is_digit(&self, at: usize) -> bool {
+        self.byte(at).is_some_and(|b| b.is_ascii_digit())
+    }
+
+    fn starts_number(&self, at: usize) -> bool {
+        self.is_digit(at) || (self.byte(at) == Some(b'.') && self.is_digit(at + 1))
+    }
+
+    fn char_at(&self, at: usize) -> Option<char> {
+        self.input[at..].chars().next()
+    }
+
+    fn is_separator(&self, at: usize) -> bool {
+        self.char_at(at)
+            .is_some_and(|c| c.is_whitespace() || c == ',')
+    }
+
+    /// Skip separators, returning the offset of a second comma in a row if there is one
+    fn skip_separators(&mut self) -> Option<usize> {
+        let mut comma = false;
+        while let Some(c) = self.char_at(self.at) {
+            if c == ',' {
+                if comma {
+                    return Some(self.at);
+                }
+                comma = true;
+            } else if !c.is_whitespace() {
+                break;
+            }
+            self.at += c.len_utf8();
+        }
+        None
+    }
+
+    /// Read a run of digits into `number`, returning the offset of a misplaced separator
+    fn digits(&mut self, number: &mut Number, fraction: bool) -> Result<(), usize> {
+        let run_start = self.at;
+        while let Some(b) = self.byte(self.at) {
+            match b {
+                b'0'..=b'9' => number.push_digit(b - b'0', fraction),
+                b'_' | b',' if self.at > run_start => {
+                    let grouping = match b {
+                        b'_' => self.is_digit(self.at + 1),
+                        _ => {
+                            !fraction
+                                && (1..=3).all(|n| self.is_digit(self.at + n))
+                                && !self.is_digit(self.at + 4)
+                        }
+                    };
+                    if !grouping {
+                        if b == b'_' || self.is_digit(self.at + 1) {
+                            return Err(self.at);
+                        }
+                        break;
+                    }
+                }
+                _ => break,
+            }
+            self.at += 1;
+        }
+        Ok(())
+    }
+
+    /// Read a number and its unit, starting at a digit or a `.` before a digit
+    fn component(&mut self) -> Token<'a> {
+        let start = self.at;
+        let mut number = Number::default();
+        let mut read = self.digits(&mut number, false);
+        if read.is_ok() && self.byte(self.at) == Some(b'.') {
+            self.at += 1;
+            read = self.digits(&mut number, true);
+        }
+        if let Err(at) = read {
+            self.at = at + 1;
+            return Token::Stray {
+                start: at,
+                end: at + 1,
+            };
+        }
+
+        // An exponent only counts when digits follow, so "1e" and "1ems" keep their "e"
+        if matches!(self.byte(self.at), Some(b'e' | b'E')) {
+            let sign = usize::from(matches!(self.byte(self.at + 1), Some(b'+' | b'-')));
+            if self.is_digit(self.at + 1 + sign) {
+                let negative = self.byte(self.at + 1) == Some(b'-');
+                self.at += 1 + sign;
+                let mut exponent: i32 = 0;
+                while let Some(b @ b'0'..=b'9') = self.byte(self.at) {
+                    exponent = (exponent * 10 + i32::from(b - b'0')).min(MAX_EXPONENT);
+                    self.at += 1;
+                }
+                let exponent = if negative { -exponent } else { exponent };
+                number.exponent = number.exponent.saturating_add(exponent);
+            }
+        }
+
+        let number_end = self.at;
+        let mut unit_start = number_end;
+        while let Some(c) = self.char_at(unit_start).filter(|c| c.is_whitespace()) {
+            unit_start += c.len_utf8();
+        }
+        let mut unit_end = unit_start;
+        while let Some(c) = self.char_at(unit_end).filter(|c| c.is_alphabetic()) {
+            unit_end += c.len_utf8();
+        }
+        if unit_end == unit_start {
+            unit_start = number_end;
+            unit_end = number_end;
+        }
+        self.at = unit_end;
+
+        Token::Component(Component {
+            number,
+            unit: &self.input[unit_start..unit_end],
+            start,
+            unit_start,
+            end: unit_end,
+        })
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        loop {
+            if let Some(comma) = self.skip_separators() {
+                self.at = comma + 1;
+                return Some(Token::Stray {
+                    start: comma,
+                    end: comma + 1,
+                });
+            }
+            if self.at == self.input.len() {
+                return None;
+            }
+            if self.starts_number(self.at) {
+                return Some(self.component());
+            }
+
+            let start = self.at;
+            while let Some(c) = self.char_at(self.at) {
+                if self.is_separator(self.at) || self.starts_number(self.at) {
+                    break;
+                }
+                self.at += c.len_utf8();
+            }
+            if !self.input[start..self.at].eq_ignore_ascii_case("and") {
+                return Some(Token::Stray {
+                    start,
+                    end: self.at,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_matches_full_parser() {
+        use crate::parse_sleep_duration;
+
+        for input in [
+            "100",
+            "1.5",
+            "-5",
+            "+5",
+            "1e3",
+            "250ms",
+            "1.5s",
+            ".5s",
+            "1e3ms",
+            "1.5e-1s",
+            "1e3m30s",
+            "1m30s",
+            "1h 2m 3s",
+            "1h, 30m",
+            "1h and 30m",
+            "1,500ms",
+            "1_000s 5ms",
+            "2 days",
+            "1 Week",
+            "500us",
+            "500µs",
+            "0.0005ms",
+            "1.000000001s",
+            "90 SECONDS",
+            "0h0m0s",
+        ] {
+            assert_eq!(
+                parse_duration_core(input).ok(),
+                Some(parse_sleep_duration(input).unwrap()),
+                "{:?}",
+                input
+            );
+        }
+        for input in ["1h banana", "1,5s", "1h,,30m", "1s 1.5", "5 bananas", "1e"] {
+            assert!(parse_duration_core(input).is_err(), "{:?}", input);
+            assert!(parse_sleep_duration(input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_core_values() {
+        assert_eq!(parse_duration_core("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(
+            parse_duration_core(" 2,500 "),
+            Ok(Duration::from_millis(2500))
+        );
+        assert_eq!(parse_duration_core("-1.5"), Ok(Duration::ZERO));
+        assert_eq!(
+            parse_duration_core("1e3m30s"),
+            Ok(Duration::from_secs(60_030))
+        );
+        assert_eq!(
+            parse_duration_core("1.5h30m"),
+            Ok(Duration::from_secs(7200))
+        );
+        assert_eq!(parse_duration_core("1e-10s"), Ok(Duration::ZERO));
+        assert_eq!(
+            parse_duration_core("0.123456789123s"),
+            Ok(Duration::from_nanos(123_456_789))
+        );
+        assert_eq!(
+            parse_duration_core("1.000000000000000000000000000000000000000001s"),
+            Ok(Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn test_core_errors() {
+        assert_eq!(parse_duration_core(""), Err(CoreParseError::Empty));
+        assert_eq!(parse_duration_core(" and "), Err(CoreParseError::Empty));
+        assert_eq!(
+            parse_duration_core("1h banana"),
+            Err(CoreParseError::UnknownToken { start: 3, end: 9 })
+        );
+        assert_eq!(
+            parse_duration_core("1h 30"),
+            Err(CoreParseError::UnknownToken { start: 3, end: 5 })
+        );
+        assert_eq!(
+            parse_duration_core("1m-10s"),
+            Err(CoreParseError::UnknownToken { start: 2, end: 3 })
+        );
+        assert_eq!(
+            parse_duration_core("1,5s"),
+            Err(CoreParseError::UnknownToken { start: 1, end: 2 })
+        );
+        assert_eq!(
+            parse_duration_core("1__0ms"),
+            Err(CoreParseError::UnknownToken { start: 1, end: 2 })
+        );
+        assert_eq!(
+            parse_duration_core("1h, ,30m"),
+            Err(CoreParseError::UnknownToken { start: 4, end: 5 })
+        );
+        assert_eq!(
+            parse_duration_core("1ems"),
+            Err(CoreParseError::UnknownUnit { start: 1, end: 4 })
+        );
+        assert_eq!(
+            parse_duration_core("99999999999999999999w"),
+            Err(CoreParseError::Overflow { start: 0, end: 21 })
+        );
+        assert_eq!(
+            parse_duration_core("1s 1e30h"),
+            Err(CoreParseError::Overflow { start: 3, end: 8 })
+        );
+
+        assert!(matches!(
+            SleepError::from(CoreParseError::UnknownUnit { start: 2, end: 4 }),
+            SleepError::InvalidDuration(ref m) if m.contains("2..4")
+        ));
+        assert!(matches!(
+            SleepError::from(CoreParseError::Empty),
+            SleepError::Empty
+        ));
+    }
+
+    #[test]
+    fn test_number_to_nanos() {
+        let number = |input: &str| match Tokens::new(input).next() {
+            Some(Token::Component(component)) => component.number,
+            other => panic!("{:?}", other),
+        };
+
+        assert_eq!(number("1.5").to_nanos(1_000), Some(1_500));
+        assert_eq!(number("1_000").to_integer(), Some(1_000));
+        assert_eq!(number("2.50").to_integer(), None);
+        assert_eq!(number("2.0").to_integer(), Some(2));
+        assert_eq!(number("1e3").to_integer(), Some(1_000));
+        assert_eq!(number("1e40").to_nanos(1), None);
+        assert_eq!(number("1e-400").to_nanos(1), Some(0));
+    }
+}
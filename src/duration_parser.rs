@@ -1,149 +1,740 @@
+use crate::config::{default_unit, tick_duration};
+use crate::core_parse::{
+    duration_from_nanos, sum_components, CoreParseError, Rejection, Token, Tokens, UNIT_ALIASES,
+};
 use crate::{Result, SleepError};
+use std::io::BufRead;
+use std::ops::Range;
+use std::path::Path;
 use std::time::Duration;
 
+/// A single `number+unit` component found by [`parse_spans`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedComponent {
+    /// Numeric value of the component
+    pub value: f64,
+    /// Unit spelling exactly as it appears in the input
+    pub unit: String,
+    /// Byte range of the component within the input
+    pub range: Range<usize>,
+}
+
+/// A parsed duration that remembers the text it was parsed from
+///
+/// Keeping the original input around makes logs and error reports show what the user
+/// actually wrote. [`Display`](std::fmt::Display) prints the source text.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::ParsedDuration;
+/// use std::time::Duration;
+///
+/// let parsed = ParsedDuration::parse("1m30s").unwrap();
+/// assert_eq!(parsed.resolved(), Duration::from_secs(90));
+/// assert_eq!(parsed.to_string(), "1m30s");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedDuration {
+    /// The input exactly as it was given
+    pub source: String,
+    /// The duration the input resolved to
+    pub duration: Duration,
+}
+
+impl ParsedDuration {
+    /// Parses `input` with [`parse_sleep_duration`] and keeps the source text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::InvalidDuration`] if the input cannot be parsed.
+    pub fn parse(input: &str) -> Result<ParsedDuration> {
+        Ok(ParsedDuration {
+            source: input.to_string(),
+            duration: parse_sleep_duration(input)?,
+        })
+    }
+
+    /// Returns the resolved duration.
+    pub fn resolved(&self) -> Duration {
+        self.duration
+    }
+}
+
+impl std::fmt::Display for ParsedDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
 /// Parse sleep duration with support for multiple formats
 ///
 /// Supports single units (e.g., "1s", "2m") and multiple units (e.g., "1m30s", "1h2m3s")
+///
+/// Components are read by the same tokenizer as [`parse_duration_core`](crate::parse_duration_core);
+/// on top of it this accepts the `~` marker, clock and ISO 8601 formats, ticks and the
+/// configured default unit, and treats separator-only input as zero.
+///
+/// With the `cache` feature, successful results are memoized in a small bounded LRU
+/// cache so that repeated identical inputs skip the parsing work.
 pub fn parse_sleep_duration(input: &str) -> Result<Duration> {
-    let input = input.trim().to_lowercase();
+    #[cfg(feature = "cache")]
+    {
+        if let Some(duration) = crate::cache::get(input) {
+            return Ok(duration);
+        }
+        let duration = parse_sleep_duration_uncached(input)?;
+        crate::cache::insert(input, duration);
+        Ok(duration)
+    }
 
-    if input.is_empty() {
+    #[cfg(not(feature = "cache"))]
+    parse_sleep_duration_uncached(input)
+}
+
+/// Parse sleep duration without consulting the parse cache
+fn parse_sleep_duration_uncached(original: &str) -> Result<Duration> {
+    let mut input = original.trim().to_lowercase();
+    // Bytes stripped from the front, to map error offsets back onto the original
+    let mut lead = original.len() - original.trim_start().len();
+    // A leading "~" marks an approximate value and does not change the result
+    if let Some(rest) = input.strip_prefix('~') {
+        let rest = rest.trim_start();
+        lead += input.len() - rest.len();
+        input = rest.to_string();
+    }
+
+    if is_separator_only(&input) {
         return Ok(Duration::ZERO);
     }
 
-    // Try to parse as plain number (default to milliseconds)
-    if let Ok(millis) = input.parse::<isize>() {
-        if millis <= 0 {
-            return Ok(Duration::ZERO);
-        }
-        return Ok(Duration::from_millis(millis as u64));
+    // ISO 8601 durations such as "PT1H30M"
+    if input.starts_with('p') {
+        return parse_iso8601(&input);
     }
 
-    // Parse time with units (single or multiple)
-    if let Some(duration) = parse_duration_with_unit(&input)? {
-        Ok(duration)
-    } else {
-        Err(SleepError::InvalidDuration(format!(
-            "Invalid sleep duration format: '{}'",
+    // Clock-style timestamps such as "1:30.500" or "1:02:03"
+    if let Some(duration) = parse_clock_format(&input)? {
+        return Ok(duration);
+    }
+
+    // Components, or a lone number in the default unit (milliseconds unless configured)
+    let tick = tick_duration();
+    let mut tick_used = false;
+    let total = sum_components(&input, default_unit().duration(1).as_nanos(), |unit| {
+        if TICK_ALIASES.contains(&unit) {
+            tick_used = true;
+            tick.map(|tick| tick.as_nanos())
+        } else {
+            unit_multiplier(unit).map(u128::from)
+        }
+    });
+    if tick_used && tick.is_none() {
+        return Err(SleepError::InvalidDuration(format!(
+            "Tick unit used in '{}' but no tick duration is configured",
             input
-        )))
+        )));
     }
+    total
+        .map(duration_from_nanos)
+        .map_err(|rejection| rejection_error(original, &input, lead, rejection))
 }
 
-/// Parse duration with single or multiple time units
-fn parse_duration_with_unit(input: &str) -> Result<Option<Duration>> {
-    use lazy_static::lazy_static;
-    use regex::Regex;
+/// Turn a rejection from the component scanner into the error reported for `original`
+///
+/// `input` is the normalized text that was scanned, which starts `lead` bytes into
+/// `original`. Stray text is only reported as [`SleepError::UnknownToken`] when some
+/// other component was recognized; otherwise the whole input is simply invalid.
+fn rejection_error(original: &str, input: &str, lead: usize, rejection: Rejection) -> SleepError {
+    match rejection.error {
+        CoreParseError::Overflow { start, end } if (start, end) != (0, input.len()) => {
+            SleepError::NumberOutOfRange(format!(
+                "Component '{}' in '{}' is too large",
+                &input[start..end],
+                input
+            ))
+        }
+        CoreParseError::Overflow { .. } => {
+            SleepError::NumberOutOfRange(format!("Duration '{}' is too large", input))
+        }
+        CoreParseError::UnknownToken { start, end }
+        | CoreParseError::UnknownUnit { start, end }
+            if rejection.recognized =>
+        {
+            let offset = start + lead;
+            let token = original
+                .get(offset..offset + end - start)
+                .unwrap_or(&input[start..end]);
+            SleepError::UnknownToken {
+                input: original.to_string(),
+                token: token.to_string(),
+                offset,
+            }
+        }
+        _ => {
+            let hint = suggest_unit(input)
+                .map(|unit| format!(" (did you mean '{}'?)", unit))
+                .unwrap_or_default();
+            SleepError::InvalidDuration(format!(
+                "Invalid sleep duration format: '{}'{}",
+                input, hint
+            ))
+        }
+    }
+}
 
-    lazy_static! {
-        // Patterns for single units (existing functionality)
-        static ref SINGLE_PATTERNS: Vec<(&'static str, f64)> = vec![
-            // Milliseconds
-            (r"^(\d+)\s*(ms|millis?|milliseconds?)$", 1.0),
-            // Seconds
-            (r"^(\d+)\s*(s|sec|seconds?)$", 1000.0),
-            // Minutes
-            (r"^(\d+)\s*(m|min|minutes?)$", 60_000.0),
-            // Hours
-            (r"^(\d+)\s*(h|hr|hours?)$", 3_600_000.0),
-            // Short format (no spaces)
-            (r"^(\d+)(ms)$", 1.0),
-            (r"^(\d+)(s)$", 1000.0),
-            (r"^(\d+)(m)$", 60_000.0),
-            (r"^(\d+)(h)$", 3_600_000.0),
-        ];
+/// Parse sleep duration, rejecting inputs that contain no duration components
+///
+/// This behaves like [`parse_sleep_duration`], except that inputs which are empty after
+/// trimming or consist only of separators (such as `","` or `"and"`) are an error instead
+/// of silently resolving to [`Duration::ZERO`].
+///
+/// # Errors
+///
+/// Returns [`SleepError::Empty`] for empty or separator-only input, and
+/// [`SleepError::InvalidDuration`] for anything [`parse_sleep_duration`] rejects.
+pub fn parse_sleep_duration_strict(input: &str) -> Result<Duration> {
+    if is_separator_only(&input.to_lowercase()) {
+        return Err(SleepError::Empty);
+    }
+    parse_sleep_duration(input)
+}
 
-        static ref FLOAT_PATTERNS: Vec<(&'static str, f64)> = vec![
-            (r"^(\d*\.?\d+)\s*(s|sec|seconds?)$", 1000.0),
-            (r"^(\d*\.?\d+)\s*(m|min|minutes?)$", 60_000.0),
-            (r"^(\d*\.?\d+)(s)$", 1000.0),
-            (r"^(\d*\.?\d+)(m)$", 60_000.0),
-        ];
+/// Parse a sleep duration that must be strictly positive
+///
+/// Required delays that silently resolve to zero, such as `"0s"` or `"-5"`, usually point
+/// at a misconfiguration, so they are rejected instead of skipping the sleep.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if the input cannot be parsed or resolves to
+/// zero.
+pub fn parse_sleep_duration_nonzero(input: &str) -> Result<Duration> {
+    let duration = parse_sleep_duration(input)?;
+    if duration.is_zero() {
+        return Err(SleepError::InvalidDuration(
+            "duration must be greater than zero".to_string(),
+        ));
     }
+    Ok(duration)
+}
 
-    // First, try single unit patterns (including float patterns)
-    for (pattern, multiplier) in SINGLE_PATTERNS.iter() {
-        let re = Regex::new(pattern).unwrap();
-        if let Some(caps) = re.captures(input) {
-            if let Ok(value) = caps[1].parse::<isize>() {
-                if value <= 0 {
-                    return Ok(Some(Duration::ZERO));
+/// Check whether the input holds nothing but whitespace, single commas and the word "and"
+///
+/// A doubled comma such as `",,"` is not a separator, so it is rejected rather than read
+/// as an empty duration.
+fn is_separator_only(input: &str) -> bool {
+    Tokens::new(input).next().is_none()
+}
+
+/// Extract the first duration embedded anywhere in a larger string
+///
+/// Unlike [`parse_sleep_duration`], which requires the whole input to be a duration, this
+/// scans for the first run of adjacent `number+unit` components (e.g. `"30s"` in
+/// `"retry after 30s please"`, or `"1h30m"` in `"wait 1h30m now"`) and returns their sum.
+/// Components may be separated by whitespace, commas or "and"; any other word ends the
+/// run. Tokens with unknown units are skipped.
+///
+/// Returns `None` if no duration token is found.
+pub fn extract_duration(input: &str) -> Option<Duration> {
+    let mut run: Option<Range<usize>> = None;
+    // "5s" in "1,5s" is not a duration of its own
+    let mut after_separator = false;
+    for token in Tokens::new(input).map(Some).chain(std::iter::once(None)) {
+        match token {
+            Some(Token::Component(component))
+                if is_known_unit(component.unit) && !after_separator =>
+            {
+                let start = run.map_or(component.start, |run| run.start);
+                run = Some(start..component.end);
+            }
+            _ => {
+                if let Some(duration) = run
+                    .take()
+                    .and_then(|run| parse_sleep_duration(&input[run]).ok())
+                {
+                    return Some(duration);
                 }
-                let millis = (value as f64 * multiplier) as u64;
-                return Ok(Some(Duration::from_millis(millis)));
             }
         }
+        after_separator = matches!(
+            token,
+            Some(Token::Stray { start, end }) if is_misplaced_separator(input, start..end)
+        );
     }
+    None
+}
 
-    // Then try float patterns (this handles "1.5s" correctly)
-    for (pattern, multiplier) in FLOAT_PATTERNS.iter() {
-        let re = Regex::new(pattern).unwrap();
-        if let Some(caps) = re.captures(input) {
-            if let Ok(value) = caps[1].parse::<f64>() {
-                if value <= 0.0 {
-                    return Ok(Some(Duration::ZERO));
-                }
-                let millis = (value * multiplier) as u64;
-                return Ok(Some(Duration::from_millis(millis)));
+/// Check whether a unit word, in any case, names a unit or a configured tick
+fn is_known_unit(unit: &str) -> bool {
+    let unit = unit.to_lowercase();
+    unit_multiplier(&unit).is_some()
+        || (TICK_ALIASES.contains(&unit.as_str()) && tick_duration().is_some())
+}
+
+/// Parse the `number+unit` components of a duration string along with their source spans
+///
+/// Each component records the byte range it occupies in `input`, which lets editors
+/// highlight components or underline errors in place.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if a component has an unknown unit or the input
+/// contains no components at all, and [`SleepError::UnknownToken`] for a misplaced digit
+/// separator such as the `,` in `"1,5s"`.
+pub fn parse_spans(input: &str) -> Result<Vec<ParsedComponent>> {
+    let mut components = Vec::new();
+    for token in Tokens::new(input) {
+        let component = match token {
+            Token::Component(component) if !component.unit.is_empty() => component,
+            Token::Stray { start, end } if is_misplaced_separator(input, start..end) => {
+                return Err(misplaced_separator(input, start));
+            }
+            _ => continue,
+        };
+        if unit_multiplier(&component.unit.to_lowercase()).is_none() {
+            return Err(SleepError::InvalidDuration(format!(
+                "Unknown unit '{}' in '{}'",
+                component.unit, input
+            )));
+        }
+        components.push(ParsedComponent {
+            value: component.number.to_f64(),
+            unit: component.unit.to_string(),
+            range: component.start..component.end,
+        });
+    }
+
+    if components.is_empty() {
+        return Err(SleepError::InvalidDuration(format!(
+            "No duration components found in '{}'",
+            input
+        )));
+    }
+    Ok(components)
+}
+
+/// Check whether stray text is a lone `_` or a `,` directly before a digit
+///
+/// The tokenizer leaves digit separators that do not group digits, as in `"1,5s"` or
+/// `"1__0ms"`, as stray text; the lenient scanners reject them instead of skipping them.
+fn is_misplaced_separator(input: &str, stray: Range<usize>) -> bool {
+    let after = input.as_bytes().get(stray.end);
+    match &input[stray] {
+        "_" => true,
+        "," => after.is_some_and(u8::is_ascii_digit),
+        _ => false,
+    }
+}
+
+/// Error for a digit separator that does not group digits
+fn misplaced_separator(input: &str, offset: usize) -> SleepError {
+    SleepError::UnknownToken {
+        input: input.to_string(),
+        token: input[offset..offset + 1].to_string(),
+        offset,
+    }
+}
+
+/// Parse a messy, human-written duration by summing every recognized component
+///
+/// This tolerant parser accepts commas, the word "and", mixed full and abbreviated unit
+/// names, fractional values and arbitrary whitespace, e.g. `"1h, 30 min and 15s"`. Words
+/// that are not attached to a number (such as "wait" or "about") are ignored. Components
+/// are summed exactly in nanoseconds.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if a number is followed by an unknown unit, a
+/// component is negative (such as `"-5s"`) or the input contains no recognizable
+/// components, [`SleepError::UnknownToken`] for a misplaced digit separator such as the
+/// `,` in `"1,5s"`, and [`SleepError::NumberOutOfRange`] if the total does not fit in a
+/// [`Duration`].
+pub fn parse_relaxed(input: &str) -> Result<Duration> {
+    let lowered = input.to_lowercase();
+    let mut total: u128 = 0;
+    let mut found_any = false;
+    for token in Tokens::new(&lowered) {
+        let component = match token {
+            Token::Component(component) if !component.unit.is_empty() => component,
+            Token::Stray { start, end } if is_misplaced_separator(&lowered, start..end) => {
+                return Err(misplaced_separator(&lowered, start));
             }
+            _ => continue,
+        };
+        if lowered[..component.start].ends_with('-') {
+            return Err(SleepError::InvalidDuration(format!(
+                "Negative component '-{}' in '{}'",
+                &lowered[component.start..component.end],
+                input
+            )));
         }
+        let multiplier = unit_multiplier(component.unit).ok_or_else(|| {
+            SleepError::InvalidDuration(format!("Unknown unit '{}' in '{}'", component.unit, input))
+        })?;
+        total = component
+            .number
+            .to_nanos(u128::from(multiplier))
+            .and_then(|nanos| total.checked_add(nanos))
+            .filter(|total| *total <= Duration::MAX.as_nanos())
+            .ok_or_else(|| SleepError::NumberOutOfRange(format!("'{}' is too large", input)))?;
+        found_any = true;
     }
 
-    // Finally, try multiple units pattern (e.g., "1h2m3s")
-    // Only if no single unit pattern matched
-    if let Some(duration) = parse_multiple_units(input)? {
-        return Ok(Some(duration));
+    if !found_any {
+        return Err(SleepError::InvalidDuration(format!(
+            "No duration components found in '{}'",
+            input
+        )));
     }
+    Ok(duration_from_nanos(total))
+}
+
+/// Parse the leading duration of a string and return the unparsed remainder
+///
+/// Consecutive `number+unit` tokens at the start of `input` are consumed and summed, so
+/// `"30s then stop"` yields `(30s, " then stop")` and `"1m 30s left"` yields
+/// `(90s, " left")`. Tokens may be separated by whitespace, commas or "and". Parsing stops
+/// at the first token that is not a known duration.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if the input does not start with a duration,
+/// and [`SleepError::UnknownToken`] if it starts with a misplaced digit separator such as
+/// the `,` in `"1,5s"`.
+pub fn parse_duration_prefix(input: &str) -> Result<(Duration, &str)> {
+    let mut prefix: Option<Range<usize>> = None;
+    for token in Tokens::new(input) {
+        let component = match token {
+            Token::Component(component) if is_known_unit(component.unit) => component,
+            Token::Stray { start, end }
+                if prefix.is_none() && is_misplaced_separator(input, start..end) =>
+            {
+                return Err(misplaced_separator(input, start));
+            }
+            _ => break,
+        };
+        let start = match prefix {
+            Some(prefix) => prefix.start,
+            None if input[..component.start].trim().is_empty() => component.start,
+            None => break,
+        };
+        prefix = Some(start..component.end);
+    }
+
+    let prefix = prefix.ok_or_else(|| {
+        SleepError::InvalidDuration(format!("No leading duration in '{}'", input))
+    })?;
+    let rest = &input[prefix.end..];
+    Ok((parse_sleep_duration(&input[prefix])?, rest))
+}
+
+/// Parse a percentage such as `"50%"` into a fraction such as `0.5`
+///
+/// The fraction can scale a base duration that is only known at runtime. Values above
+/// 100% are allowed; whitespace around the number and before the `%` sign is ignored.
+///
+/// # Errors
+///
+/// Returns [`SleepError::ParseError`] if the input is not a number followed by `%`, and
+/// [`SleepError::NumberOutOfRange`] if the percentage is negative or not finite.
+pub fn parse_percent(input: &str) -> Result<f64> {
+    let number = input
+        .trim()
+        .strip_suffix('%')
+        .map(str::trim_end)
+        .ok_or_else(|| SleepError::ParseError(format!("Expected a percentage: '{}'", input)))?;
+    let percent: f64 = number
+        .parse()
+        .map_err(|_| SleepError::ParseError(format!("Invalid percentage: '{}'", input)))?;
+    if !(percent.is_finite() && percent >= 0.0) {
+        return Err(SleepError::NumberOutOfRange(format!(
+            "Percentage must be finite and non-negative: '{}'",
+            input
+        )));
+    }
+    Ok(percent / 100.0)
+}
+
+/// Parse a comma-separated list of durations
+///
+/// Each item is parsed with [`parse_sleep_duration`], so `"100ms, 1s, 1m30s"` yields three
+/// durations in order.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if any item is empty or cannot be parsed.
+pub fn parse_schedule(input: &str) -> Result<Vec<Duration>> {
+    input
+        .split(',')
+        .map(|item| {
+            let item = item.trim();
+            if item.is_empty() {
+                return Err(SleepError::InvalidDuration(format!(
+                    "Empty item in schedule: '{}'",
+                    input
+                )));
+            }
+            parse_sleep_duration(item)
+        })
+        .collect()
+}
+
+/// Parse a comma-separated list of relative gaps into absolute offsets
+///
+/// Each entry of the result is the running sum of the gaps so far, so `"0s,1s,2s"`
+/// yields `[0s, 1s, 3s]`. This complements [`parse_schedule`], which returns the gaps
+/// themselves.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if any item is empty or cannot be parsed.
+pub fn cumulative_schedule(input: &str) -> Result<Vec<Duration>> {
+    let mut offset = Duration::ZERO;
+    Ok(parse_schedule(input)?
+        .into_iter()
+        .map(|gap| {
+            offset = offset.saturating_add(gap);
+            offset
+        })
+        .collect())
+}
+
+/// Read a sleep duration from a file
+///
+/// The file contents are trimmed and parsed with [`parse_sleep_duration`], which makes it
+/// easy to keep a tunable delay in a file such as one under `/etc`.
+///
+/// # Errors
+///
+/// Returns [`SleepError::ParseError`] (including the path) if the file cannot be read, or
+/// [`SleepError::InvalidDuration`] if its contents are not a valid duration.
+pub fn parse_from_file<P: AsRef<Path>>(path: P) -> Result<Duration> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        SleepError::ParseError(format!("Failed to read '{}': {}", path.display(), e))
+    })?;
+    parse_sleep_duration(contents.trim())
+}
 
-    Ok(None)
+/// Parse durations from a reader, one per line
+///
+/// Each line is trimmed and parsed with [`parse_sleep_duration`]. Blank lines and lines
+/// starting with `#` are skipped, and every other line yields its own [`Result`], so a
+/// bad line can be reported without abandoning the rest of the input.
+///
+/// A line that cannot be read yields [`SleepError::ParseError`].
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::parse_lines;
+/// use std::time::Duration;
+///
+/// let input = "# delays\n100ms\n\n2s\n";
+/// let delays: Vec<Duration> = parse_lines(input.as_bytes()).map(Result::unwrap).collect();
+/// assert_eq!(delays, [Duration::from_millis(100), Duration::from_secs(2)]);
+/// ```
+pub fn parse_lines<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Duration>> {
+    reader.lines().filter_map(|line| match line {
+        Ok(line) => {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                None
+            } else {
+                Some(parse_sleep_duration(line))
+            }
+        }
+        Err(e) => Some(Err(SleepError::ParseError(format!(
+            "Failed to read line: {}",
+            e
+        )))),
+    })
 }
 
-/// Parse multiple time units in a single string
-fn parse_multiple_units(input: &str) -> Result<Option<Duration>> {
+/// Parse an ISO 8601 duration such as `PT1H30M`, `PT0.5S` or `P1DT2H`
+///
+/// Weeks, days, hours, minutes and seconds are supported, each with an optional
+/// fraction. Years and months have no fixed length and are rejected.
+fn parse_iso8601(input: &str) -> Result<Duration> {
     use lazy_static::lazy_static;
     use regex::Regex;
 
     lazy_static! {
-        // Improved pattern to avoid matching float numbers
-        static ref MULTI_UNIT_PATTERN: Regex = Regex::new(r"(?i)(\d+)\s*([a-z]+)").unwrap();
+        static ref ISO_8601: Regex = Regex::new(
+            r"^p(?:(\d+(?:\.\d+)?)w)?(?:(\d+(?:\.\d+)?)d)?(?:t(?:(\d+(?:\.\d+)?)h)?(?:(\d+(?:\.\d+)?)m)?(?:(\d+(?:\.\d+)?)s)?)?$"
+        )
+        .unwrap();
     }
+    const UNIT_SECS: [f64; 5] = [604_800.0, 86_400.0, 3600.0, 60.0, 1.0];
 
-    let mut total_millis: u64 = 0;
-    let mut found_any = false;
-    let mut has_positive_value = false;
+    let invalid = || {
+        SleepError::InvalidDuration(format!(
+            "Invalid ISO 8601 duration (only W, D, H, M and S are supported): '{}'",
+            input
+        ))
+    };
+    let caps = ISO_8601.captures(input).ok_or_else(invalid)?;
+    // "P", "PT" and "P1DT" are not valid durations
+    let has_time_part = input.contains('t');
+    let time_components = (3..=5).filter(|&i| caps.get(i).is_some()).count();
+    let components = (1..=5).filter(|&i| caps.get(i).is_some()).count();
+    if components == 0 || (has_time_part && time_components == 0) {
+        return Err(invalid());
+    }
 
-    for caps in MULTI_UNIT_PATTERN.captures_iter(input) {
-        let value: u64 = match caps[1].parse() {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
+    let mut total_secs = 0.0;
+    for (index, unit_secs) in UNIT_SECS.iter().enumerate() {
+        if let Some(value) = caps.get(index + 1) {
+            let value: f64 = value.as_str().parse().map_err(|_| invalid())?;
+            total_secs += value * unit_secs;
+        }
+    }
+    Duration::try_from_secs_f64(total_secs)
+        .map_err(|e| SleepError::NumberOutOfRange(format!("'{}': {}", input, e)))
+}
 
-        let unit = &caps[2].to_lowercase();
-        let multiplier = match unit.as_str() {
-            "ms" | "milli" | "millis" | "millisecond" | "milliseconds" => 1,
-            "s" | "sec" | "second" | "seconds" => 1000,
-            "m" | "min" | "minute" | "minutes" => 60_000,
-            "h" | "hr" | "hour" | "hours" => 3_600_000,
-            _ => continue, // Skip unknown units
-        };
+/// Parse `MM:SS[.fff]` and `HH:MM:SS[.fff]` timestamps
+///
+/// Only the final (seconds) field may carry a fraction. Every field after the first must
+/// be below 60.
+fn parse_clock_format(input: &str) -> Result<Option<Duration>> {
+    if !input.contains(':') {
+        return Ok(None);
+    }
 
-        total_millis += value * multiplier;
-        found_any = true;
-        if value > 0 {
-            has_positive_value = true;
+    let invalid =
+        || SleepError::InvalidDuration(format!("Invalid clock-style duration: '{}'", input));
+    let fields: Vec<&str> = input.split(':').collect();
+    if fields.len() > 3 {
+        return Err(invalid());
+    }
+
+    let (seconds_field, leading) = fields.split_last().ok_or_else(invalid)?;
+    let (whole, fraction) = match seconds_field.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (*seconds_field, ""),
+    };
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(whole) || !(fraction.is_empty() || is_digits(fraction)) {
+        return Err(invalid());
+    }
+
+    let mut total_secs: u64 = 0;
+    for (index, field) in leading.iter().enumerate() {
+        if !is_digits(field) {
+            return Err(invalid());
+        }
+        let value: u64 = field.parse().map_err(|_| invalid())?;
+        if index > 0 && value >= 60 {
+            return Err(invalid());
         }
+        total_secs = total_secs
+            .checked_mul(60)
+            .and_then(|t| t.checked_add(value))
+            .ok_or_else(invalid)?;
     }
 
-    if found_any {
-        // Return Duration::ZERO for all-zero values like "0h0m0s"
-        if has_positive_value {
-            Ok(Some(Duration::from_millis(total_millis)))
-        } else {
-            Ok(Some(Duration::ZERO))
+    let seconds: u64 = whole.parse().map_err(|_| invalid())?;
+    if seconds >= 60 {
+        return Err(invalid());
+    }
+    total_secs = total_secs
+        .checked_mul(60)
+        .and_then(|t| t.checked_add(seconds))
+        .ok_or_else(invalid)?;
+
+    // Interpret the fraction to nanosecond precision, ignoring extra digits
+    let nanos = format!("{:0<9}", &fraction[..fraction.len().min(9)])
+        .parse::<u32>()
+        .map_err(|_| invalid())?;
+    Ok(Some(Duration::new(total_secs, nanos)))
+}
+
+/// Unit spellings that count multiples of the configured tick duration
+const TICK_ALIASES: &[&str] = &["t", "tick", "ticks"];
+
+/// List every unit spelling accepted by [`parse_sleep_duration`]
+///
+/// The list is derived from the same table the parser uses, so it always matches what
+/// the parser accepts. Useful for autocomplete and help output. The tick units (`"t"`,
+/// `"tick"`, `"ticks"`) are only included while a tick duration is configured with
+/// [`set_tick_duration`](crate::set_tick_duration).
+pub fn recognized_units() -> Vec<&'static str> {
+    let mut units: Vec<&'static str> = UNIT_ALIASES.iter().map(|(alias, _)| *alias).collect();
+    if tick_duration().is_some() {
+        units.extend_from_slice(TICK_ALIASES);
+    }
+    units
+}
+
+/// Look up the nanosecond multiplier of a lowercase unit spelling
+pub(crate) fn unit_multiplier(unit: &str) -> Option<u64> {
+    UNIT_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == unit)
+        .map(|(_, multiplier)| *multiplier)
+}
+
+/// Suggest a known unit for the first unrecognized word in the input
+///
+/// Only close matches (edit distance of at most 2) are suggested.
+fn suggest_unit(input: &str) -> Option<&'static str> {
+    input
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| word.len() > 2 && *word != "and" && unit_multiplier(word).is_none())
+        .find_map(|word| {
+            UNIT_ALIASES
+                .iter()
+                .map(|(alias, _)| (edit_distance(word, alias), *alias))
+                .filter(|(distance, _)| *distance <= 2)
+                .min_by_key(|(distance, _)| *distance)
+                .map(|(_, alias)| alias)
+        })
+}
+
+/// Levenshtein distance between two ASCII strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.bytes().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
         }
-    } else {
-        Ok(None)
     }
+
+    row[b.len()]
+}
+
+/// List the `(value, multiplier)` components of a multi-unit string, before summation
+///
+/// This exposes the component tokenizer used by [`parse_sleep_duration`], so `"1m30s"`
+/// yields `[(1, 60_000), (30, 1_000)]`. Multipliers are in milliseconds; tick units use
+/// the configured tick duration. Unknown units, units finer than a millisecond, values
+/// with a fractional part and values that do not fit in a `u64` are skipped.
+pub fn scan_units(input: &str) -> Vec<(u64, u64)> {
+    let lowered = input.to_lowercase();
+    Tokens::new(&lowered)
+        .filter_map(|token| {
+            let component = match token {
+                Token::Component(component) => component,
+                Token::Stray { .. } => return None,
+            };
+            let value = u64::try_from(component.number.to_integer()?).ok()?;
+            let unit = component.unit;
+            let multiplier = if TICK_ALIASES.contains(&unit) {
+                u64::try_from(tick_duration()?.as_millis()).ok()?
+            } else {
+                unit_multiplier(unit)? / 1_000_000
+            };
+            (multiplier > 0).then_some((value, multiplier))
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -200,6 +791,99 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_multiple_units_overflow() {
+        // Values that don't fit a u64 used to be skipped silently
+        for input in [
+            "1s99999999999999999999h",
+            "1h 999999999999999999999999 ms",
+            "99999999999999999999h 1s",
+        ] {
+            match parse_sleep_duration(input) {
+                Err(SleepError::NumberOutOfRange(message)) => {
+                    assert!(message.contains("99999999999999999999"), "{}", message)
+                }
+                other => panic!("{}: {:?}", input, other),
+            }
+        }
+
+        // Components that fit individually but not in a Duration
+        assert!(matches!(
+            parse_sleep_duration("18446744073709551615w 18446744073709551615w"),
+            Err(SleepError::NumberOutOfRange(_))
+        ));
+        assert!(matches!(
+            parse_sleep_duration("99999999999999999999h"),
+            Err(SleepError::NumberOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_digit_separators() -> Result<()> {
+        assert_eq!(
+            parse_sleep_duration("1,000s")?,
+            parse_sleep_duration("1000s")?
+        );
+        assert_eq!(
+            parse_sleep_duration("1,500ms")?,
+            Duration::from_millis(1500)
+        );
+        assert_eq!(parse_sleep_duration("1_000ms")?, Duration::from_secs(1));
+        assert_eq!(parse_sleep_duration("1,000,000us")?, Duration::from_secs(1));
+        assert_eq!(parse_sleep_duration("2,500")?, Duration::from_millis(2500));
+        assert_eq!(
+            parse_sleep_duration("1s,500ms")?,
+            Duration::from_millis(1500)
+        );
+
+        // Misplaced separators are not grouping and still fail. A doubled comma is not a
+        // separator either, so unlike "," it is an error rather than an empty duration.
+        for input in ["1,ms", "1_ms", "_1ms", "1,5s", "1,0000ms", "1__0ms", ",,"] {
+            assert!(parse_sleep_duration(input).is_err(), "{:?}", input);
+        }
+        match parse_sleep_duration("1,5s") {
+            Err(SleepError::UnknownToken { token, offset, .. }) => {
+                assert_eq!((token.as_str(), offset), (",", 1));
+            }
+            other => panic!("{:?}", other),
+        }
+
+        // The lenient scanners group digits the same way
+        assert_eq!(
+            extract_duration("took 1,500ms"),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(parse_relaxed("1,000ms and 2s")?, Duration::from_secs(3));
+        let spans = parse_spans("1,000ms")?;
+        assert_eq!((spans[0].value, spans[0].range.clone()), (1000.0, 0..7));
+        assert_eq!(
+            parse_duration_prefix("1,500ms rest")?,
+            (Duration::from_millis(1500), " rest")
+        );
+        assert_eq!(extract_duration("took 1,5s"), None);
+        for result in [
+            parse_relaxed("1,5s").map(|_| Vec::new()),
+            parse_spans("1,5s"),
+            parse_duration_prefix("1,5s").map(|_| Vec::new()),
+        ] {
+            match result {
+                Err(SleepError::UnknownToken { token, offset, .. }) => {
+                    assert_eq!((token.as_str(), offset), (",", 1));
+                }
+                other => panic!("{:?}", other),
+            }
+        }
+
+        // Error offsets point into the original input
+        match parse_sleep_duration("1_000s 5x") {
+            Err(SleepError::UnknownToken { token, offset, .. }) => {
+                assert_eq!((token.as_str(), offset), ("x", 8));
+            }
+            other => panic!("{:?}", other),
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_float_numbers_not_matched_as_multi_units() -> Result<()> {
         // These should be parsed as single units with float values, not multiple units
@@ -230,4 +914,623 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_single_space_unit_matrix() -> Result<()> {
+        let units: [(&[&str], u64); 4] = [
+            (&["ms", "milli", "millis", "millisecond", "milliseconds"], 1),
+            (&["s", "sec", "secs", "second", "seconds"], 1000),
+            (&["m", "min", "mins", "minute", "minutes"], 60_000),
+            (&["h", "hr", "hrs", "hour", "hours"], 3_600_000),
+        ];
+
+        for (aliases, millis) in units {
+            for alias in aliases {
+                assert_eq!(
+                    parse_sleep_duration(&format!("5 {}", alias))?,
+                    Duration::from_millis(5 * millis),
+                    "5 {}",
+                    alias
+                );
+                assert_eq!(
+                    parse_sleep_duration(&format!("0 {}", alias))?,
+                    Duration::ZERO,
+                    "0 {}",
+                    alias
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parsed_duration() -> Result<()> {
+        let parsed = ParsedDuration::parse(" 1m 30s ")?;
+        assert_eq!(parsed.resolved(), Duration::from_secs(90));
+        assert_eq!(parsed.source, " 1m 30s ");
+        assert_eq!(parsed.to_string(), " 1m 30s ");
+        assert_eq!(
+            format!("waiting {}", ParsedDuration::parse("250")?),
+            "waiting 250"
+        );
+
+        assert!(ParsedDuration::parse("bogus").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_day_and_week_units() -> Result<()> {
+        let day = Duration::from_secs(86_400);
+        assert_eq!(parse_sleep_duration("2d")?, 2 * day);
+        assert_eq!(parse_sleep_duration("1 day")?, day);
+        assert_eq!(parse_sleep_duration("3 days")?, 3 * day);
+        assert_eq!(parse_sleep_duration("1w")?, 7 * day);
+        assert_eq!(parse_sleep_duration("2 weeks")?, 14 * day);
+        assert_eq!(parse_sleep_duration("1.5d")?, day + day / 2);
+        assert_eq!(
+            parse_sleep_duration("1w2d3h")?,
+            9 * day + Duration::from_secs(3 * 3600)
+        );
+        assert_eq!(parse_sleep_duration("100000d")?, 100_000 * day);
+
+        // Totals beyond Duration::MAX are reported instead of wrapping or saturating
+        for input in [
+            "99999999999999999w",
+            "30000000000000000d",
+            "1d 99999999999999999w",
+        ] {
+            assert!(
+                matches!(
+                    parse_sleep_duration(input),
+                    Err(SleepError::NumberOutOfRange(_))
+                ),
+                "{}",
+                input
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_approximate_marker() -> Result<()> {
+        for input in ["500ms", "1m30s", "1.5s", "250"] {
+            assert_eq!(
+                parse_sleep_duration(&format!("~{}", input))?,
+                parse_sleep_duration(input)?,
+                "{}",
+                input
+            );
+        }
+        assert_eq!(parse_sleep_duration(" ~ 2s")?, Duration::from_secs(2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_token_offset() {
+        let unknown = |input: &str| match parse_sleep_duration(input) {
+            Err(SleepError::UnknownToken {
+                input: source,
+                token,
+                offset,
+            }) => {
+                assert_eq!(source, input);
+                assert_eq!(&input[offset..offset + token.len()], token);
+                (token, offset)
+            }
+            other => panic!("{:?} for {}", other, input),
+        };
+
+        assert_eq!(unknown("1h banana 3s"), ("banana".to_string(), 3));
+        assert_eq!(unknown("1h 5 bananas"), ("bananas".to_string(), 5));
+        assert_eq!(unknown("5s please"), ("please".to_string(), 3));
+        assert_eq!(unknown("1m-10s"), ("-".to_string(), 2));
+        // Offsets refer to the original, untrimmed input
+        assert_eq!(unknown("  ~ 1H Banana"), ("Banana".to_string(), 7));
+
+        // Without any recognized component the input is simply invalid
+        assert!(matches!(
+            parse_sleep_duration("5 banana"),
+            Err(SleepError::InvalidDuration(_))
+        ));
+    }
+
+    #[test]
+    fn test_millisec_spelling() -> Result<()> {
+        assert_eq!(
+            parse_sleep_duration("100millisec")?,
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            parse_sleep_duration("100 millisecs")?,
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            parse_sleep_duration("1.5 millisec")?,
+            Duration::from_micros(1500)
+        );
+        assert_eq!(
+            parse_sleep_duration("1s 100millisecs")?,
+            Duration::from_millis(1100)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sub_millisecond_units() -> Result<()> {
+        assert_eq!(parse_sleep_duration("500us")?, Duration::from_micros(500));
+        assert_eq!(parse_sleep_duration("500µs")?, Duration::from_micros(500));
+        assert_eq!(parse_sleep_duration("500μs")?, Duration::from_micros(500));
+        assert_eq!(
+            parse_sleep_duration("250 microseconds")?,
+            Duration::from_micros(250)
+        );
+        assert_eq!(parse_sleep_duration("100ns")?, Duration::from_nanos(100));
+        assert_eq!(parse_sleep_duration("7 nanos")?, Duration::from_nanos(7));
+
+        // Combined units accumulate nanoseconds instead of truncating to milliseconds
+        assert_eq!(
+            parse_sleep_duration("1s500us")?,
+            Duration::from_micros(1_000_500)
+        );
+        assert_eq!(
+            parse_sleep_duration("1ms 250us 5ns")?,
+            Duration::from_nanos(1_250_005)
+        );
+        assert_eq!(parse_sleep_duration("1.5us")?, Duration::from_nanos(1500));
+        assert_eq!(parse_sleep_duration("0.25ms")?, Duration::from_micros(250));
+
+        assert_eq!(parse_sleep_duration("0us")?, Duration::ZERO);
+        assert_eq!(parse_sleep_duration("0ns")?, Duration::ZERO);
+        Ok(())
+    }
+
+    #[test]
+    fn test_float_values_keep_single_value_semantics() -> Result<()> {
+        // Previously these fell through to the multi-unit scanner and lost the integer part
+        assert_eq!(parse_sleep_duration("1.5 ms")?, Duration::from_micros(1500));
+        assert_eq!(parse_sleep_duration("0.5h")?, Duration::from_secs(1800));
+        assert_eq!(parse_sleep_duration("1.5 hr")?, Duration::from_secs(5400));
+        assert_eq!(parse_sleep_duration("0.0 ms")?, Duration::ZERO);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scientific_notation() -> Result<()> {
+        assert_eq!(parse_sleep_duration("1e3ms")?, Duration::from_secs(1));
+        assert_eq!(parse_sleep_duration("1.5e-1s")?, Duration::from_millis(150));
+        assert_eq!(parse_sleep_duration("2E+1 s")?, Duration::from_secs(20));
+        assert_eq!(parse_sleep_duration("1e3")?, Duration::from_secs(1));
+        assert_eq!(parse_sleep_duration("0e5ms")?, Duration::ZERO);
+        assert!(parse_sleep_duration("1e").is_err());
+        assert!(parse_sleep_duration("1ems").is_err());
+        assert!(matches!(
+            parse_sleep_duration("1e400s"),
+            Err(SleepError::NumberOutOfRange(_))
+        ));
+        assert!(matches!(
+            parse_sleep_duration("1e400"),
+            Err(SleepError::NumberOutOfRange(_))
+        ));
+        assert_eq!(
+            parse_sleep_duration("1e3m30s")?,
+            Duration::from_secs(60_030)
+        );
+
+        // The lenient scanners read exponents the same way
+        assert_eq!(
+            extract_duration("wait 1e3m30s now"),
+            Some(Duration::from_secs(60_030))
+        );
+        assert_eq!(
+            parse_relaxed("about 1.5e-1s and 1E3ms")?,
+            Duration::from_millis(1150)
+        );
+        let spans = parse_spans("1.5e3ms")?;
+        assert_eq!((spans[0].value, spans[0].range.clone()), (1500.0, 0..7));
+        assert_eq!(
+            parse_duration_prefix("1e3m30s left")?,
+            (Duration::from_secs(60_030), " left")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unitless_floats() -> Result<()> {
+        // The default unit is milliseconds
+        assert_eq!(parse_sleep_duration("1.5")?, Duration::from_micros(1500));
+        assert_eq!(parse_sleep_duration("-1.5")?, Duration::ZERO);
+        assert!(parse_sleep_duration("1.5.2").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_clock_format() -> Result<()> {
+        assert_eq!(
+            parse_sleep_duration("1:30.500")?,
+            Duration::from_millis(90500)
+        );
+        assert_eq!(
+            parse_sleep_duration("0:00.250")?,
+            Duration::from_millis(250)
+        );
+        assert_eq!(parse_sleep_duration("1:02:03")?, Duration::from_secs(3723));
+        assert_eq!(
+            parse_sleep_duration("01:00:00.5")?,
+            Duration::from_millis(3_600_500)
+        );
+        assert_eq!(parse_sleep_duration("90:00")?, Duration::from_secs(5400));
+
+        for input in ["1:60", "1:60:00", "1:2:3:4", "1.5:30", "1:", ":30", "1:3x"] {
+            assert!(parse_sleep_duration(input).is_err(), "{}", input);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_separator_only_inputs() -> Result<()> {
+        for input in ["", "   ", ",", "and", " , and ,"] {
+            assert_eq!(parse_sleep_duration(input)?, Duration::ZERO, "{:?}", input);
+            assert!(
+                matches!(parse_sleep_duration_strict(input), Err(SleepError::Empty)),
+                "{:?}",
+                input
+            );
+        }
+
+        assert_eq!(
+            parse_sleep_duration_strict("1m and 30s")?,
+            Duration::from_secs(90)
+        );
+        assert_eq!(parse_sleep_duration_strict("0s")?, Duration::ZERO);
+        assert!(matches!(
+            parse_sleep_duration_strict("sand"),
+            Err(SleepError::InvalidDuration(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_sleep_duration_nonzero() -> Result<()> {
+        assert_eq!(
+            parse_sleep_duration_nonzero("1ms")?,
+            Duration::from_millis(1)
+        );
+        for input in ["0s", "-5", "0"] {
+            assert!(
+                matches!(
+                    parse_sleep_duration_nonzero(input),
+                    Err(SleepError::InvalidDuration(ref msg)) if msg == "duration must be greater than zero"
+                ),
+                "{}",
+                input
+            );
+        }
+        assert!(parse_sleep_duration_nonzero("bogus").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_recognized_units() -> Result<()> {
+        let units = recognized_units();
+        for alias in [
+            "ms",
+            "millis",
+            "millisecond",
+            "s",
+            "seconds",
+            "min",
+            "hr",
+            "hours",
+        ] {
+            assert!(units.contains(&alias), "{}", alias);
+        }
+
+        for alias in units {
+            let multiplier = unit_multiplier(alias).unwrap();
+            assert_eq!(
+                parse_sleep_duration(&format!("3{}", alias))?,
+                Duration::from_nanos(3 * multiplier),
+                "{}",
+                alias
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_typo_suggestions() {
+        let message = |input: &str| match parse_sleep_duration(input) {
+            Err(SleepError::InvalidDuration(msg)) => msg,
+            other => panic!("expected InvalidDuration for {:?}, got {:?}", input, other),
+        };
+
+        assert!(message("1 secnd").contains("did you mean 'second'?"));
+        assert!(message("1 minnute").contains("did you mean 'minute'?"));
+        assert!(message("5 hourz").contains("did you mean 'hour'?"));
+        assert!(!message("5 banana").contains("did you mean"));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("second", "second"), 0);
+        assert_eq!(edit_distance("secnd", "second"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "ms"), 2);
+    }
+
+    #[test]
+    fn test_extract_duration() {
+        assert_eq!(
+            extract_duration("retry after 30s please"),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            extract_duration("Took 1.5 seconds, then 2m"),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            extract_duration("3 apples and 10ms"),
+            Some(Duration::from_millis(10))
+        );
+        assert_eq!(extract_duration("wait a little while"), None);
+        assert_eq!(extract_duration("version 42"), None);
+
+        // Adjacent components form a single duration
+        assert_eq!(
+            extract_duration("wait 1h30m now"),
+            Some(Duration::from_secs(5400))
+        );
+        assert_eq!(
+            extract_duration("ETA: 2 minutes and 5 seconds, then done"),
+            Some(Duration::from_secs(125))
+        );
+        assert_eq!(
+            extract_duration("after 1m 5 apples 30s"),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[cfg(feature = "cache")]
+    #[test]
+    fn test_cached_results_match_uncached() -> Result<()> {
+        for input in ["1m30s", "250ms", "1.5s", "0", "2 hours"] {
+            let uncached = parse_sleep_duration_uncached(input)?;
+            assert_eq!(parse_sleep_duration(input)?, uncached);
+            assert_eq!(crate::cache::get(input), Some(uncached));
+            assert_eq!(parse_sleep_duration(input)?, uncached);
+        }
+
+        // Failures are never cached
+        assert!(parse_sleep_duration("bogus").is_err());
+        assert_eq!(crate::cache::get("bogus"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_spans() -> Result<()> {
+        let spans = parse_spans("1h30m")?;
+        assert_eq!(
+            spans,
+            vec![
+                ParsedComponent {
+                    value: 1.0,
+                    unit: "h".to_string(),
+                    range: 0..2,
+                },
+                ParsedComponent {
+                    value: 30.0,
+                    unit: "m".to_string(),
+                    range: 2..5,
+                },
+            ]
+        );
+
+        let input = "wait 1.5 Seconds";
+        let spans = parse_spans(input)?;
+        assert_eq!(spans.len(), 1);
+        assert_eq!(&input[spans[0].range.clone()], "1.5 Seconds");
+
+        assert!(parse_spans("1h 5 bananas").is_err());
+        assert!(parse_spans("nothing here").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_relaxed() -> Result<()> {
+        assert_eq!(
+            parse_relaxed("1h, 30 min and 15s")?,
+            Duration::from_secs(5415)
+        );
+        assert_eq!(
+            parse_relaxed("wait about 2 Minutes, and 30 seconds")?,
+            Duration::from_secs(150)
+        );
+        assert_eq!(
+            parse_relaxed("1.5 hours and 250ms")?,
+            Duration::from_millis(5_400_250)
+        );
+        assert_eq!(parse_relaxed("  10s,5s ")?, Duration::from_secs(15));
+
+        assert!(parse_relaxed("soon").is_err());
+        assert!(parse_relaxed("").is_err());
+        assert!(parse_relaxed("1h and 5 bananas").is_err());
+
+        // Negative components are rejected rather than counted as positive
+        for input in ["-5s", "1m -10s", "wait -2 minutes"] {
+            assert!(
+                matches!(parse_relaxed(input), Err(SleepError::InvalidDuration(_))),
+                "{}",
+                input
+            );
+        }
+
+        // The sum is exact and overflow is reported rather than saturated
+        assert_eq!(parse_relaxed("0.1s and 0.2s")?, Duration::from_millis(300));
+        assert_eq!(
+            parse_relaxed("1ns, 1w")?,
+            Duration::from_secs(604_800) + Duration::from_nanos(1)
+        );
+        assert!(matches!(
+            parse_relaxed("18446744073709551615s and 1s"),
+            Err(SleepError::NumberOutOfRange(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_duration_prefix() -> Result<()> {
+        assert_eq!(
+            parse_duration_prefix("30s then stop")?,
+            (Duration::from_secs(30), " then stop")
+        );
+        assert_eq!(
+            parse_duration_prefix("1m 30s left")?,
+            (Duration::from_secs(90), " left")
+        );
+        assert_eq!(
+            parse_duration_prefix("1.5s, 2 apples")?,
+            (Duration::from_millis(1500), ", 2 apples")
+        );
+        assert_eq!(
+            parse_duration_prefix("250ms")?,
+            (Duration::from_millis(250), "")
+        );
+
+        assert!(parse_duration_prefix("then 30s").is_err());
+        assert!(parse_duration_prefix("5 apples").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_scan_units() {
+        assert_eq!(scan_units("1m30s"), [(1, 60_000), (30, 1_000)]);
+        assert_eq!(
+            scan_units("2 Hours, 5 min and 0s"),
+            [(2, 3_600_000), (5, 60_000), (0, 1_000)]
+        );
+        assert_eq!(scan_units("1w 250ms"), [(1, 604_800_000), (250, 1)]);
+
+        // Unknown and sub-millisecond units are skipped
+        assert_eq!(scan_units("3 bananas 4s 500us"), [(4, 1_000)]);
+        assert!(scan_units("nothing here").is_empty());
+    }
+
+    #[test]
+    fn test_iso8601() -> Result<()> {
+        assert_eq!(parse_sleep_duration("PT1H30M")?, Duration::from_secs(5400));
+        assert_eq!(parse_sleep_duration("PT0.5S")?, Duration::from_millis(500));
+        assert_eq!(
+            parse_sleep_duration("P1DT2H")?,
+            Duration::from_secs(26 * 3600)
+        );
+        assert_eq!(
+            parse_sleep_duration("P2D")?,
+            Duration::from_secs(2 * 86_400)
+        );
+        assert_eq!(parse_sleep_duration("P1W")?, Duration::from_secs(604_800));
+        assert_eq!(parse_sleep_duration("PT45S")?, Duration::from_secs(45));
+        assert_eq!(
+            parse_sleep_duration(" pt1m30.25s ")?,
+            Duration::from_millis(90_250)
+        );
+        assert_eq!(parse_sleep_duration("PT0S")?, Duration::ZERO);
+
+        for malformed in ["P", "PT", "P1DT", "P1Y", "P1M", "PT1S1M", "PTXS", "P1H"] {
+            assert!(
+                matches!(
+                    parse_sleep_duration(malformed),
+                    Err(SleepError::InvalidDuration(_))
+                ),
+                "{}",
+                malformed
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_percent() -> Result<()> {
+        assert_eq!(parse_percent("50%")?, 0.5);
+        assert_eq!(parse_percent("100%")?, 1.0);
+        assert_eq!(parse_percent(" 12.5 % ")?, 0.125);
+        assert_eq!(parse_percent("250%")?, 2.5);
+        assert_eq!(parse_percent("0%")?, 0.0);
+
+        assert!(matches!(
+            parse_percent("-10%"),
+            Err(SleepError::NumberOutOfRange(_))
+        ));
+        for malformed in ["50", "%", "abc%", "50%%", "inf%"] {
+            assert!(parse_percent(malformed).is_err(), "{}", malformed);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_schedule() -> Result<()> {
+        assert_eq!(
+            parse_schedule("100ms, 1s, 1m30s")?,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_secs(1),
+                Duration::from_secs(90),
+            ]
+        );
+        assert!(parse_schedule("1s,,2s").is_err());
+        assert!(parse_schedule("1s,bogus").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cumulative_schedule() -> Result<()> {
+        assert_eq!(
+            cumulative_schedule("0s,1s,2s")?,
+            vec![
+                Duration::ZERO,
+                Duration::from_secs(1),
+                Duration::from_secs(3)
+            ]
+        );
+        assert_eq!(
+            cumulative_schedule("250ms")?,
+            vec![Duration::from_millis(250)]
+        );
+        assert!(cumulative_schedule("1s,").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_lines() -> Result<()> {
+        let input = "# startup delays\n250ms\n\n   \n  1m30s  \n# retry\nbogus\n5s";
+        let mut results = parse_lines(input.as_bytes());
+        assert_eq!(results.next().unwrap()?, Duration::from_millis(250));
+        assert_eq!(results.next().unwrap()?, Duration::from_secs(90));
+        assert!(matches!(
+            results.next(),
+            Some(Err(SleepError::InvalidDuration(_)))
+        ));
+        assert_eq!(results.next().unwrap()?, Duration::from_secs(5));
+        assert!(results.next().is_none());
+
+        assert_eq!(parse_lines(&b"\n# nothing\n"[..]).count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_from_file() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("sleep-utils-{}.txt", std::process::id()));
+        std::fs::write(&path, "250ms\n").unwrap();
+        let parsed = parse_from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(parsed?, Duration::from_millis(250));
+
+        let missing = path.with_extension("missing");
+        match parse_from_file(&missing) {
+            Err(SleepError::ParseError(msg)) => assert!(msg.contains("sleep-utils-")),
+            other => panic!("expected ParseError, got {:?}", other),
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,465 @@
+use crate::core_parse::duration_from_nanos;
+use crate::{Result, SleepError, SleepInput};
+use std::cmp::Reverse;
+use std::time::{Duration, Instant};
+
+/// Computes the greatest common divisor of several durations.
+///
+/// The GCD is computed on the nanosecond representation of each duration, which
+/// makes it suitable for driving several periodic tasks from a single timer tick.
+/// Zero durations do not affect the result unless every duration is zero.
+///
+/// Returns `None` if the iterator is empty.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::gcd_interval;
+/// use std::time::Duration;
+///
+/// let tick = gcd_interval([Duration::from_millis(100), Duration::from_millis(150)]);
+/// assert_eq!(tick, Some(Duration::from_millis(50)));
+///
+/// assert_eq!(gcd_interval(Vec::<Duration>::new()), None);
+/// ```
+pub fn gcd_interval<I: IntoIterator<Item = Duration>>(durations: I) -> Option<Duration> {
+    let mut iter = durations.into_iter();
+    let first = iter.next()?.as_nanos();
+    let nanos = iter.fold(first, |acc, d| gcd(acc, d.as_nanos()));
+    Some(duration_from_nanos(nanos))
+}
+
+/// Resolves a sleep input without sleeping.
+///
+/// This is meant for preflighting configuration: every field can be checked at load
+/// time, with the same semantics [`smart_sleep`](crate::smart_sleep) will later apply.
+/// Zero and negative numbers resolve to [`Duration::ZERO`]. The global time scale and
+/// granularity are not applied.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if text input cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::validate_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(validate_duration("1m30s").unwrap(), Duration::from_secs(90));
+/// assert_eq!(validate_duration(-5).unwrap(), Duration::ZERO);
+/// assert!(validate_duration("5 banana").is_err());
+/// ```
+pub fn validate_duration(input: impl Into<SleepInput>) -> Result<Duration> {
+    input.into().to_duration()
+}
+
+/// Resolves several sleep inputs and returns the shortest duration.
+///
+/// Every input is resolved with [`SleepInput::to_duration`], so zero and negative
+/// values count as [`Duration::ZERO`].
+///
+/// # Errors
+///
+/// Returns the error of the first input that cannot be resolved, or
+/// [`SleepError::InvalidDuration`] if the iterator is empty.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::resolve_min;
+/// use std::time::Duration;
+///
+/// let shortest = resolve_min(["1s", "500ms", "2s"]).unwrap();
+/// assert_eq!(shortest, Duration::from_millis(500));
+/// ```
+pub fn resolve_min<I, S>(inputs: I) -> Result<Duration>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<SleepInput>,
+{
+    let mut min: Option<Duration> = None;
+    for input in inputs {
+        let duration = input.into().to_duration()?;
+        min = Some(min.map_or(duration, |m| m.min(duration)));
+    }
+    min.ok_or_else(|| SleepError::InvalidDuration("No inputs provided".to_string()))
+}
+
+/// Resolves two sleep inputs and returns the longer duration.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if either input cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::max_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(max_duration("1s", "500ms").unwrap(), Duration::from_secs(1));
+/// ```
+pub fn max_duration(a: impl Into<SleepInput>, b: impl Into<SleepInput>) -> Result<Duration> {
+    Ok(a.into().to_duration()?.max(b.into().to_duration()?))
+}
+
+/// Resolves two sleep inputs and returns the shorter duration.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if either input cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::min_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(min_duration("1s", 500).unwrap(), Duration::from_millis(500));
+/// ```
+pub fn min_duration(a: impl Into<SleepInput>, b: impl Into<SleepInput>) -> Result<Duration> {
+    Ok(a.into().to_duration()?.min(b.into().to_duration()?))
+}
+
+/// Checks whether two durations differ by at most `tolerance`.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::durations_approx_equal;
+/// use std::time::Duration;
+///
+/// let ms = Duration::from_millis;
+/// assert!(durations_approx_equal(ms(100), ms(103), ms(5)));
+/// assert!(!durations_approx_equal(ms(100), ms(110), ms(5)));
+/// ```
+pub fn durations_approx_equal(a: Duration, b: Duration, tolerance: Duration) -> bool {
+    a.abs_diff(b) <= tolerance
+}
+
+/// Resolves a sleep input and returns the instant that far in the future.
+///
+/// This is handy for deadline propagation: the result can be passed around and later
+/// handed to a sleep-until style helper. If the deadline cannot be represented, the
+/// furthest representable instant is returned instead.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if the input cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::deadline_from_now;
+/// use std::time::{Duration, Instant};
+///
+/// let deadline = deadline_from_now("1m").unwrap();
+/// assert!(deadline > Instant::now() + Duration::from_secs(59));
+/// ```
+pub fn deadline_from_now(input: impl Into<SleepInput>) -> Result<Instant> {
+    let mut duration = input.into().to_duration()?;
+    let now = Instant::now();
+    loop {
+        match now.checked_add(duration) {
+            Some(deadline) => return Ok(deadline),
+            None => duration /= 2,
+        }
+    }
+}
+
+/// Returns how far along a span of `total` started at `start` is, from 0.0 to 1.0.
+///
+/// The fraction is `start.elapsed() / total`, clamped to `1.0` once the span is over.
+/// A zero `total` is always complete. This lets UIs render progress on their own
+/// schedule without sleeping.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::progress_fraction;
+/// use std::time::{Duration, Instant};
+///
+/// let started = Instant::now() - Duration::from_secs(10);
+/// assert_eq!(progress_fraction(started, Duration::from_secs(5)), 1.0);
+/// ```
+pub fn progress_fraction(start: Instant, total: Duration) -> f64 {
+    if total.is_zero() {
+        return 1.0;
+    }
+    (start.elapsed().as_secs_f64() / total.as_secs_f64()).clamp(0.0, 1.0)
+}
+
+/// Splits a total budget into `count` nearly equal intervals.
+///
+/// The intervals sum exactly to the resolved total: leftover nanoseconds that do not
+/// divide evenly are handed out one each to the earliest intervals, so no two intervals
+/// differ by more than a nanosecond. To pace `n` items across a window, ask for `n - 1`
+/// intervals, one between each pair of items.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if the input cannot be parsed, or
+/// [`SleepError::NumberOutOfRange`] if `count` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::even_intervals;
+/// use std::time::Duration;
+///
+/// let intervals = even_intervals("1s", 3).unwrap();
+/// assert_eq!(intervals[0], Duration::from_nanos(333_333_334));
+/// assert_eq!(intervals.iter().sum::<Duration>(), Duration::from_secs(1));
+/// ```
+pub fn even_intervals(total: impl Into<SleepInput>, count: usize) -> Result<Vec<Duration>> {
+    if count == 0 {
+        return Err(SleepError::NumberOutOfRange(
+            "Cannot split a duration into zero intervals".to_string(),
+        ));
+    }
+    let nanos = total.into().to_duration()?.as_nanos();
+    let parts = count as u128;
+    let (share, leftover) = (nanos / parts, nanos % parts);
+    Ok((0..parts)
+        .map(|i| duration_from_nanos(share + u128::from(i < leftover)))
+        .collect())
+}
+
+/// Describes how far `actual` is from `expected`, for timing test failure messages.
+///
+/// The difference is printed in the most readable unit, so a failed assertion can say
+/// `"actual was 12ms longer than expected"` instead of dumping two raw durations.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::describe_duration_diff;
+/// use std::time::Duration;
+///
+/// let ms = Duration::from_millis;
+/// assert_eq!(
+///     describe_duration_diff(ms(100), ms(112)),
+///     "actual was 12ms longer than expected"
+/// );
+/// assert_eq!(
+///     describe_duration_diff(ms(2000), ms(500)),
+///     "actual was 1.5s shorter than expected"
+/// );
+/// ```
+pub fn describe_duration_diff(expected: Duration, actual: Duration) -> String {
+    let diff = actual.abs_diff(expected);
+    if actual > expected {
+        format!("actual was {:?} longer than expected", diff)
+    } else if actual < expected {
+        format!("actual was {:?} shorter than expected", diff)
+    } else {
+        format!("actual matched the expected {:?}", expected)
+    }
+}
+
+/// Snaps a duration to the closest of a set of allowed values.
+///
+/// This suits devices that only accept fixed delay steps. The allowed values don't need
+/// to be sorted, and when `duration` is exactly halfway between two of them the larger
+/// one wins.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if `allowed` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::snap_to_allowed;
+/// use std::time::Duration;
+///
+/// let steps = [10, 50, 100, 500].map(Duration::from_millis);
+/// assert_eq!(
+///     snap_to_allowed(Duration::from_millis(80), &steps).unwrap(),
+///     Duration::from_millis(100)
+/// );
+/// ```
+pub fn snap_to_allowed(duration: Duration, allowed: &[Duration]) -> Result<Duration> {
+    allowed
+        .iter()
+        .copied()
+        .min_by_key(|&step| (step.abs_diff(duration), Reverse(step)))
+        .ok_or_else(|| SleepError::InvalidDuration("No allowed durations provided".to_string()))
+}
+
+/// Euclid's algorithm on nanosecond counts
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_interval() {
+        assert_eq!(
+            gcd_interval([Duration::from_millis(100), Duration::from_millis(150)]),
+            Some(Duration::from_millis(50))
+        );
+        assert_eq!(
+            gcd_interval([Duration::from_secs(3)]),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            gcd_interval([Duration::ZERO, Duration::from_millis(40)]),
+            Some(Duration::from_millis(40))
+        );
+        assert_eq!(gcd_interval(Vec::new()), None);
+    }
+
+    #[test]
+    fn test_validate_duration() -> Result<()> {
+        assert_eq!(validate_duration("250ms")?, Duration::from_millis(250));
+        assert_eq!(validate_duration(100)?, Duration::from_millis(100));
+        assert_eq!(validate_duration(-100)?, Duration::ZERO);
+        assert_eq!(
+            validate_duration(Duration::from_secs(3))?,
+            Duration::from_secs(3)
+        );
+
+        for input in ["abc", "5 banana"] {
+            assert!(
+                matches!(
+                    validate_duration(input),
+                    Err(SleepError::InvalidDuration(_))
+                ),
+                "{}",
+                input
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_min() -> Result<()> {
+        assert_eq!(
+            resolve_min(["1s", "500ms", "2s"])?,
+            Duration::from_millis(500)
+        );
+        assert_eq!(resolve_min([100, -5])?, Duration::ZERO);
+
+        assert!(resolve_min(["1s", "bogus"]).is_err());
+        assert!(resolve_min(Vec::<&str>::new()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_max_duration() -> Result<()> {
+        assert_eq!(max_duration("1s", "500ms")?, Duration::from_secs(1));
+        assert_eq!(min_duration("1s", "500ms")?, Duration::from_millis(500));
+        assert_eq!(
+            max_duration(Duration::from_secs(2), "1m")?,
+            Duration::from_secs(60)
+        );
+        assert_eq!(min_duration(-10, "1m")?, Duration::ZERO);
+
+        assert!(max_duration("1s", "bogus").is_err());
+        assert!(min_duration("bogus", "1s").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_durations_approx_equal() {
+        let ms = Duration::from_millis;
+        assert!(durations_approx_equal(ms(100), ms(100), Duration::ZERO));
+        assert!(durations_approx_equal(ms(100), ms(105), ms(5)));
+        assert!(durations_approx_equal(ms(105), ms(100), ms(5)));
+        assert!(!durations_approx_equal(ms(100), ms(106), ms(5)));
+        assert!(!durations_approx_equal(ms(106), ms(100), ms(5)));
+    }
+
+    #[test]
+    fn test_deadline_from_now() -> Result<()> {
+        let before = Instant::now();
+        let deadline = deadline_from_now("500ms")?;
+        let after = Instant::now();
+        assert!(deadline >= before + Duration::from_millis(500));
+        assert!(deadline <= after + Duration::from_millis(500));
+
+        assert!(deadline_from_now(0)? <= Instant::now());
+        assert!(deadline_from_now(Duration::MAX)? > Instant::now());
+        assert!(deadline_from_now("bogus").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_progress_fraction() {
+        let hour = Duration::from_secs(3600);
+        assert!(progress_fraction(Instant::now(), hour) < 0.001);
+
+        let half = progress_fraction(Instant::now() - Duration::from_secs(1800), hour);
+        assert!((0.5..0.51).contains(&half), "{}", half);
+
+        let past = Instant::now() - Duration::from_secs(2 * 3600);
+        assert_eq!(progress_fraction(past, hour), 1.0);
+        assert_eq!(progress_fraction(Instant::now(), Duration::ZERO), 1.0);
+    }
+
+    #[test]
+    fn test_even_intervals() -> Result<()> {
+        let intervals = even_intervals("1s", 3)?;
+        assert_eq!(intervals.len(), 3);
+        assert_eq!(intervals.iter().sum::<Duration>(), Duration::from_secs(1));
+        assert_eq!(
+            intervals,
+            [333_333_334, 333_333_333, 333_333_333].map(Duration::from_nanos)
+        );
+
+        let intervals = even_intervals("1m30s", 7)?;
+        assert_eq!(intervals.iter().sum::<Duration>(), Duration::from_secs(90));
+        let longest = intervals.iter().max().copied().unwrap_or_default();
+        let shortest = intervals.iter().min().copied().unwrap_or_default();
+        assert!(longest - shortest <= Duration::from_nanos(1));
+
+        assert_eq!(even_intervals(0, 2)?, [Duration::ZERO; 2]);
+        assert!(even_intervals("1s", 0).is_err());
+        assert!(even_intervals("bogus", 2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_describe_duration_diff() {
+        let ms = Duration::from_millis;
+        assert_eq!(
+            describe_duration_diff(ms(100), ms(112)),
+            "actual was 12ms longer than expected"
+        );
+        assert_eq!(
+            describe_duration_diff(ms(100), ms(99)),
+            "actual was 1ms shorter than expected"
+        );
+        assert_eq!(
+            describe_duration_diff(ms(100), ms(100) + Duration::from_micros(250)),
+            "actual was 250µs longer than expected"
+        );
+        assert_eq!(
+            describe_duration_diff(ms(50), ms(50)),
+            "actual matched the expected 50ms"
+        );
+    }
+
+    #[test]
+    fn test_snap_to_allowed() -> Result<()> {
+        let ms = Duration::from_millis;
+        let allowed = [ms(10), ms(50), ms(100)];
+        assert_eq!(snap_to_allowed(ms(30), &allowed)?, ms(50));
+        assert_eq!(snap_to_allowed(ms(50), &allowed)?, ms(50));
+        assert_eq!(snap_to_allowed(ms(75), &[ms(100), ms(50)])?, ms(100));
+        assert_eq!(snap_to_allowed(ms(1), &allowed)?, ms(10));
+        assert_eq!(snap_to_allowed(ms(900), &allowed)?, ms(100));
+        assert!(snap_to_allowed(ms(30), &[]).is_err());
+        Ok(())
+    }
+}
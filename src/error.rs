@@ -1,7 +1,22 @@
+use alloc::string::String;
 use thiserror::Error;
 
 /// Sleep utilities error types
+///
+/// This enum is `#[non_exhaustive]`: new variants may be added in minor releases, so
+/// downstream `match` expressions must include a wildcard `_ =>` arm.
+///
+/// ```
+/// use sleep_utils::{parse_sleep_duration, SleepError};
+///
+/// match parse_sleep_duration("invalid") {
+///     Ok(duration) => println!("parsed {:?}", duration),
+///     Err(SleepError::InvalidDuration(msg)) => println!("invalid: {}", msg),
+///     Err(other) => println!("other error: {}", other),
+/// }
+/// ```
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum SleepError {
     /// Returned when a duration string cannot be parsed
     #[error("Invalid duration format: {0}")]
@@ -14,10 +29,60 @@ pub enum SleepError {
     /// Returned when numeric values are outside valid range
     #[error("Number out of range: {0}")]
     NumberOutOfRange(String),
+
+    /// Returned when a duration contains a token that belongs to no known component
+    ///
+    /// For `"1h banana 3s"` the token is `"banana"` at byte offset 3.
+    #[error("Unrecognized token '{token}' at byte {offset} in '{input}'")]
+    UnknownToken {
+        /// The full input that was being parsed
+        input: String,
+        /// The unrecognized token
+        token: String,
+        /// Byte offset of the token within `input`
+        offset: usize,
+    },
+
+    /// Returned by strict parsing when the input contains no duration components
+    #[error("Empty duration input")]
+    Empty,
 }
 
 /// Result type alias for sleep-utils operations
 ///
 /// This is a convenience type that uses [`SleepError`] as the error type
 /// for all functions in this crate.
-pub type Result<T> = std::result::Result<T, SleepError>;
+pub type Result<T> = core::result::Result<T, SleepError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_error_display() {
+        let errors = [
+            SleepError::InvalidDuration("abc".to_string()),
+            SleepError::ParseError("abc".to_string()),
+            SleepError::NumberOutOfRange("abc".to_string()),
+            SleepError::UnknownToken {
+                input: "1h abc".to_string(),
+                token: "abc".to_string(),
+                offset: 3,
+            },
+            SleepError::Empty,
+        ];
+        let rendered: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            rendered,
+            [
+                "Invalid duration format: abc",
+                "Parse error: abc",
+                "Number out of range: abc",
+                "Unrecognized token 'abc' at byte 3 in '1h abc'",
+                "Empty duration input",
+            ]
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use crate::core_parse::duration_from_nanos;
+use crate::{Result, SleepError, SleepInput};
+use std::fmt;
+use std::time::Duration;
+
+/// Largest-first units used when formatting, as (suffix, nanoseconds)
+const FORMAT_UNITS: [(&str, u128); 6] = [
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+/// Formats a duration as compact components such as `"1h2m3s"`, omitting zeros.
+///
+/// This is the inverse of [`parse_sleep_duration`](crate::parse_sleep_duration): units
+/// are emitted largest first, and `parse_sleep_duration(&format_duration(d))` returns
+/// `d` again. Hours are the largest unit, so a day renders as `"24h"`, and `ms` only
+/// appears when there is a sub-second remainder. Sub-millisecond remainders are kept as
+/// `us` and `ns` components rather than rounded away. A zero duration renders as `"0ms"`.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{format_duration, parse_sleep_duration};
+/// use std::time::Duration;
+///
+/// let formatted = format_duration(Duration::from_millis(3_723_250));
+/// assert_eq!(formatted, "1h2m3s250ms");
+/// assert_eq!(parse_sleep_duration(&formatted).unwrap(), Duration::from_millis(3_723_250));
+/// ```
+pub fn format_duration(duration: Duration) -> String {
+    let mut nanos = duration.as_nanos();
+    if nanos == 0 {
+        return "0ms".to_string();
+    }
+
+    let mut out = String::new();
+    for (suffix, unit) in FORMAT_UNITS {
+        let count = nanos / unit;
+        if count > 0 {
+            out.push_str(&format!("{}{}", count, suffix));
+            nanos %= unit;
+        }
+    }
+    out
+}
+
+/// Prefix of the encoding produced by [`encode_duration`]
+const ENCODING_PREFIX: &str = "ns:";
+
+/// Encodes a duration as its total nanoseconds, such as `"ns:90500000000"`.
+///
+/// Unlike [`format_duration`], this is meant for machines: the format is stable, always
+/// lossless and trivial to parse in other languages. Decode it with [`decode_duration`].
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{decode_duration, encode_duration};
+/// use std::time::Duration;
+///
+/// let encoded = encode_duration(Duration::from_micros(1500));
+/// assert_eq!(encoded, "ns:1500000");
+/// assert_eq!(decode_duration(&encoded).unwrap(), Duration::from_micros(1500));
+/// ```
+pub fn encode_duration(duration: Duration) -> String {
+    format!("{}{}", ENCODING_PREFIX, duration.as_nanos())
+}
+
+/// Decodes a duration produced by [`encode_duration`].
+///
+/// Only the exact `ns:<digits>` form is accepted; no whitespace, signs or units.
+///
+/// # Errors
+///
+/// Returns [`SleepError::ParseError`] if the input is not in the encoded form, or
+/// [`SleepError::NumberOutOfRange`] if the count exceeds [`Duration::MAX`].
+pub fn decode_duration(encoded: &str) -> Result<Duration> {
+    let digits = encoded
+        .strip_prefix(ENCODING_PREFIX)
+        .filter(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+        .ok_or_else(|| {
+            SleepError::ParseError(format!(
+                "Expected an 'ns:<digits>' duration, got '{}'",
+                encoded
+            ))
+        })?;
+    digits
+        .parse::<u128>()
+        .ok()
+        .filter(|nanos| *nanos <= Duration::MAX.as_nanos())
+        .map(duration_from_nanos)
+        .ok_or_else(|| {
+            SleepError::NumberOutOfRange(format!("Encoded duration '{}' is too large", encoded))
+        })
+}
+
+/// Renders the input as the canonical form of the duration it resolves to.
+///
+/// Numbers and durations are formatted directly, text is shown as its normalized parse
+/// (`"90 seconds"` renders as `"1m30s"`) and a deadline as the time remaining. Text that
+/// doesn't parse is shown unchanged, and a NaN or infinite float as its raw value, such
+/// as `"NaNms"`. See [`SleepInput::to_duration`] for how inputs are resolved; the global
+/// time scale is not applied.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::SleepInput;
+/// use std::time::Duration;
+///
+/// assert_eq!(SleepInput::Number(90_000).to_string(), "1m30s");
+/// assert_eq!(SleepInput::from("2 hours").to_string(), "2h");
+/// assert_eq!(SleepInput::from(Duration::from_micros(1500)).to_string(), "1ms500us");
+/// ```
+impl fmt::Display for SleepInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self, self.to_duration()) {
+            (_, Ok(duration)) => f.write_str(&format_duration(duration)),
+            (SleepInput::Text(text), Err(_)) => f.write_str(text),
+            (SleepInput::Float(value), Err(_)) => write!(f, "{}ms", value),
+            (_, Err(_)) => f.write_str("?"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_sleep_duration;
+
+    #[test]
+    fn test_format_duration() {
+        let cases = [
+            (Duration::ZERO, "0ms"),
+            (Duration::from_millis(250), "250ms"),
+            (Duration::from_secs(3600), "1h"),
+            (Duration::from_secs(7200), "2h"),
+            (Duration::from_secs(86_400), "24h"),
+            (Duration::from_secs(3723), "1h2m3s"),
+            (Duration::from_millis(3_600_500), "1h500ms"),
+            (Duration::from_nanos(1_000_001), "1ms1ns"),
+        ];
+        for (duration, expected) in cases {
+            let formatted = format_duration(duration);
+            assert_eq!(formatted, expected);
+            assert_eq!(parse_sleep_duration(&formatted).unwrap(), duration);
+        }
+    }
+
+    #[test]
+    fn test_format_duration_round_trips() {
+        // A small xorshift generator keeps the sample reproducible without a dependency
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..10_000 {
+            // Up to about 30 years, at millisecond granularity
+            let duration = Duration::from_millis(next() % 1_000_000_000_000);
+            let formatted = format_duration(duration);
+            assert_eq!(
+                parse_sleep_duration(&formatted).unwrap(),
+                duration,
+                "{}",
+                formatted
+            );
+            assert!(!formatted.contains("us") && !formatted.contains("ns"));
+            if duration.subsec_millis() == 0 {
+                assert!(!formatted.contains("ms"), "{}", formatted);
+            }
+        }
+
+        for _ in 0..1_000 {
+            let duration = Duration::from_nanos(next() % 10_000_000_000_000);
+            let formatted = format_duration(duration);
+            assert_eq!(
+                parse_sleep_duration(&formatted).unwrap(),
+                duration,
+                "{}",
+                formatted
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_duration_round_trips() -> Result<()> {
+        for duration in [
+            Duration::ZERO,
+            Duration::from_nanos(1),
+            Duration::from_nanos(1_500),
+            Duration::from_micros(250_001),
+            Duration::from_millis(90_500),
+            Duration::MAX,
+        ] {
+            assert_eq!(decode_duration(&encode_duration(duration))?, duration);
+        }
+        assert_eq!(
+            encode_duration(Duration::from_millis(90_500)),
+            "ns:90500000000"
+        );
+
+        for malformed in [
+            "", "ns:", "90500", "ns: 1", "ns:-1", "ns:1.5", "ms:1", "NS:1",
+        ] {
+            assert!(
+                matches!(decode_duration(malformed), Err(SleepError::ParseError(_))),
+                "{:?}",
+                malformed
+            );
+        }
+        let too_large = format!("ns:{}", Duration::MAX.as_nanos() + 1);
+        assert!(matches!(
+            decode_duration(&too_large),
+            Err(SleepError::NumberOutOfRange(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sleep_input_display() {
+        assert_eq!(SleepInput::Number(90_000).to_string(), "1m30s");
+        assert_eq!(SleepInput::Number(-5).to_string(), "0ms");
+        assert_eq!(
+            SleepInput::from(Duration::from_millis(5_400_250)).to_string(),
+            "1h30m250ms"
+        );
+        assert_eq!(SleepInput::from("90 seconds").to_string(), "1m30s");
+        assert_eq!(SleepInput::from("1 banana").to_string(), "1 banana");
+        assert_eq!(SleepInput::from(2.5).to_string(), "2ms500us");
+        assert_eq!(SleepInput::from(f64::NAN).to_string(), "NaNms");
+    }
+}
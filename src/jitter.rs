@@ -0,0 +1,254 @@
+use crate::core_parse::duration_from_nanos;
+use crate::{smart_sleep, Result, SleepError, SleepInput};
+use std::time::Duration;
+
+/// Lower bound for a jittered sleep of a non-zero base duration
+const MIN_JITTER_SLEEP: Duration = Duration::from_millis(1);
+
+/// Sleeps for a random duration around `base` to desynchronize retries.
+///
+/// The duration is drawn uniformly from `[base * (1 - f), base * (1 + f)]` where `f` is
+/// `jitter_fraction`. For a non-zero base the result never drops below 1ms (or `base`
+/// itself, if that is shorter), so a large fraction cannot turn the sleep into a no-op.
+/// Zero and negative bases do not sleep.
+///
+/// Returns the jittered duration, before the global time scale, granularity and sleep
+/// limit are applied. Only available with the `rand` feature.
+///
+/// # Errors
+///
+/// Returns [`SleepError::NumberOutOfRange`] if `jitter_fraction` is outside `[0.0, 1.0]`,
+/// or [`SleepError::InvalidDuration`] if `base` cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::smart_sleep_jitter;
+/// use std::time::Duration;
+///
+/// let slept = smart_sleep_jitter("10ms", 0.5).unwrap();
+/// assert!(slept >= Duration::from_millis(5) && slept <= Duration::from_millis(15));
+/// ```
+#[track_caller]
+pub fn smart_sleep_jitter<S: Into<SleepInput>>(base: S, jitter_fraction: f64) -> Result<Duration> {
+    if !(0.0..=1.0).contains(&jitter_fraction) {
+        return Err(SleepError::NumberOutOfRange(format!(
+            "Jitter fraction must be within [0.0, 1.0], got {}",
+            jitter_fraction
+        )));
+    }
+
+    let duration = jittered(base.into().to_duration()?, jitter_fraction);
+    smart_sleep(duration)?;
+    Ok(duration)
+}
+
+/// Like [`smart_sleep_jitter`], but clamps out-of-range jitter fractions instead of
+/// failing.
+///
+/// Fractions below 0.0 (and NaN) are treated as 0.0, meaning no jitter, and fractions
+/// above 1.0 as 1.0. With the `tracing` feature a warning is logged whenever a fraction
+/// is clamped. Returns the jittered duration, before the global time scale, granularity
+/// and sleep limit are applied.
+///
+/// Only available with the `rand` feature.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if `base` cannot be parsed.
+#[track_caller]
+pub fn smart_sleep_jitter_lenient<S: Into<SleepInput>>(
+    base: S,
+    jitter_fraction: f64,
+) -> Result<Duration> {
+    let clamped = if jitter_fraction.is_nan() {
+        0.0
+    } else {
+        jitter_fraction.clamp(0.0, 1.0)
+    };
+    #[cfg(feature = "tracing")]
+    if clamped != jitter_fraction {
+        tracing::warn!(
+            requested = jitter_fraction,
+            clamped,
+            "jitter fraction outside [0.0, 1.0] was clamped"
+        );
+    }
+    smart_sleep_jitter(base, clamped)
+}
+
+/// Sleeps for a random duration drawn uniformly from `[min, max]`.
+///
+/// This suits polling loops that should wander between a floor and a ceiling instead of
+/// jittering around a center. Equal bounds sleep exactly that long. Returns the drawn
+/// duration, before the global time scale, granularity and sleep limit are applied. Only
+/// available with the `rand` feature.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if either bound cannot be parsed or `min` is
+/// greater than `max`.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::smart_sleep_range;
+/// use std::time::Duration;
+///
+/// let slept = smart_sleep_range("2ms", "5ms").unwrap();
+/// assert!(slept >= Duration::from_millis(2) && slept <= Duration::from_millis(5));
+/// assert!(smart_sleep_range("5ms", "2ms").is_err());
+/// ```
+#[track_caller]
+pub fn smart_sleep_range(
+    min: impl Into<SleepInput>,
+    max: impl Into<SleepInput>,
+) -> Result<Duration> {
+    let (min, max) = (min.into().to_duration()?, max.into().to_duration()?);
+    if min > max {
+        return Err(SleepError::InvalidDuration(format!(
+            "Range minimum {:?} is greater than maximum {:?}",
+            min, max
+        )));
+    }
+
+    let duration = random_between(min, max);
+    smart_sleep(duration)?;
+    Ok(duration)
+}
+
+/// Draw a uniformly random duration within `[min, max]`, which must be ordered
+fn random_between(min: Duration, max: Duration) -> Duration {
+    duration_from_nanos(fastrand::u128(min.as_nanos()..=max.as_nanos()))
+}
+
+/// Draw a random duration within `base * (1 ± fraction)`, floored for non-zero bases
+pub(crate) fn jittered(base: Duration, fraction: f64) -> Duration {
+    if base.is_zero() {
+        return Duration::ZERO;
+    }
+
+    let factor = 1.0 + fraction * (fastrand::f64() * 2.0 - 1.0);
+    let duration = Duration::try_from_secs_f64(base.as_secs_f64() * factor).unwrap_or(base);
+    duration.max(MIN_JITTER_SLEEP.min(base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jitter_never_below_floor() {
+        let base = Duration::from_millis(2);
+        for _ in 0..10_000 {
+            let duration = jittered(base, 0.9);
+            assert!(duration >= MIN_JITTER_SLEEP, "{:?}", duration);
+            assert!(duration <= Duration::from_micros(3800), "{:?}", duration);
+        }
+
+        assert_eq!(jittered(Duration::ZERO, 0.9), Duration::ZERO);
+        let tiny = Duration::from_micros(10);
+        assert!(jittered(tiny, 1.0) >= tiny.min(MIN_JITTER_SLEEP));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let base = Duration::from_millis(100);
+        let (low, high) = (Duration::from_millis(75), Duration::from_millis(125));
+        let mut below = false;
+        let mut above = false;
+        for _ in 0..10_000 {
+            let duration = jittered(base, 0.25);
+            assert!(duration >= low && duration <= high, "{:?}", duration);
+            below |= duration < base;
+            above |= duration > base;
+        }
+        // Draws cover both sides of the base
+        assert!(below && above);
+
+        for _ in 0..100 {
+            assert_eq!(jittered(base, 0.0), base);
+        }
+    }
+
+    #[test]
+    fn test_random_between_bounds() {
+        let (min, max) = (Duration::from_millis(10), Duration::from_millis(20));
+        let mut draws = Vec::with_capacity(10_000);
+        for _ in 0..10_000 {
+            let duration = random_between(min, max);
+            assert!(duration >= min && duration <= max, "{:?}", duration);
+            draws.push(duration);
+        }
+        // Draws spread over the whole span rather than sticking to one end
+        assert!(draws.iter().any(|d| *d < Duration::from_millis(11)));
+        assert!(draws.iter().any(|d| *d > Duration::from_millis(19)));
+
+        assert_eq!(random_between(max, max), max);
+    }
+
+    #[test]
+    fn test_smart_sleep_range() -> Result<()> {
+        for _ in 0..10 {
+            let slept = smart_sleep_range("1ms", Duration::from_millis(3))?;
+            assert!(slept >= Duration::from_millis(1) && slept <= Duration::from_millis(3));
+        }
+        assert_eq!(smart_sleep_range("2ms", "2ms")?, Duration::from_millis(2));
+
+        assert!(matches!(
+            smart_sleep_range("3ms", "1ms"),
+            Err(SleepError::InvalidDuration(_))
+        ));
+        assert!(smart_sleep_range("bogus", "1ms").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_smart_sleep_jitter_bounds() -> Result<()> {
+        for _ in 0..20 {
+            let slept = smart_sleep_jitter("4ms", 0.5)?;
+            assert!(
+                slept >= Duration::from_millis(2) && slept <= Duration::from_millis(6),
+                "{:?}",
+                slept
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_smart_sleep_jitter() -> Result<()> {
+        let slept = smart_sleep_jitter("2ms", 0.9)?;
+        assert!(slept >= MIN_JITTER_SLEEP);
+        assert_eq!(smart_sleep_jitter(0, 0.5)?, Duration::ZERO);
+
+        assert!(matches!(
+            smart_sleep_jitter("2ms", 1.5),
+            Err(SleepError::NumberOutOfRange(_))
+        ));
+        assert!(smart_sleep_jitter("2ms", f64::NAN).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_smart_sleep_jitter_lenient_clamps() -> Result<()> {
+        // Negative fractions clamp to 0.0, so there is no jitter at all
+        for _ in 0..20 {
+            assert_eq!(
+                smart_sleep_jitter_lenient("2ms", -0.5)?,
+                Duration::from_millis(2)
+            );
+        }
+        assert_eq!(
+            smart_sleep_jitter_lenient("2ms", f64::NAN)?,
+            Duration::from_millis(2)
+        );
+
+        // Fractions above 1.0 clamp to 1.0
+        for _ in 0..20 {
+            let slept = smart_sleep_jitter_lenient("2ms", 1.5)?;
+            assert!(slept <= Duration::from_millis(4), "{:?}", slept);
+        }
+        Ok(())
+    }
+}
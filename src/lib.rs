@@ -7,10 +7,11 @@
 //!
 //! - **Multiple input formats**: numbers, text, `Duration` objects
 //! - **Automatic zero/negative handling**: no sleep for zero or negative values
-//! - **Multiple time units**: milliseconds, seconds, minutes, hours
+//! - **Multiple time units**: nanoseconds, microseconds, milliseconds, seconds, minutes, hours,
+//!   days, weeks
 //! - **Combined units**: support for formats like `"1m30s"`, `"1h2m3s"`
 //! - **Platform compatibility**: uses `isize` for cross-platform support
-//! - **High performance**: optimized regex parsing with lazy static patterns
+//! - **High performance**: a single-pass tokenizer with no regex on the main parse path
 //!
 //! # Examples
 //!
@@ -52,19 +53,142 @@
 //! - [`SleepError::InvalidDuration`] when parsing invalid duration strings
 //! - [`SleepError::ParseError`] when encountering parse errors
 //! - [`SleepError::NumberOutOfRange`] when numbers are out of valid range
+//!
+//! # `no_std`
+//!
+//! Everything above needs the default `std` feature. Without it the crate is `no_std` (with
+//! `alloc`) and exports only `parse_duration_core`, `CoreParseError`, `Unit` and the error
+//! types; `parse_sleep_duration` delegates to the same core, so both read components the
+//! same way.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 
-use std::time::Duration;
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "tokio")]
+mod async_support;
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "std")]
+mod cancel;
+#[cfg(feature = "std")]
+mod capped;
+#[cfg(feature = "std")]
+mod channel;
+#[cfg(feature = "std")]
+mod config;
+mod core_parse;
+#[cfg(feature = "std")]
 mod duration_parser;
+#[cfg(feature = "std")]
+mod duration_utils;
 mod error;
+#[cfg(feature = "std")]
+mod format;
+#[cfg(feature = "rand")]
+mod jitter;
+#[cfg(feature = "std")]
+mod locale;
+#[cfg(feature = "std")]
+mod observers;
+#[cfg(feature = "std")]
+mod pacing;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "std")]
+mod parse_options;
+#[cfg(feature = "std")]
+mod precise;
+#[cfg(feature = "std")]
+mod references;
+#[cfg(feature = "std")]
+mod retry;
+#[cfg(feature = "serde")]
+mod serde_support;
+#[cfg(feature = "std")]
+mod sleeper;
+#[cfg(feature = "std")]
 mod smart_sleep;
+#[cfg(feature = "stats")]
+mod stats;
+mod unit;
+#[cfg(all(windows, feature = "windows-timer"))]
+mod windows_timer;
 
-pub use duration_parser::parse_sleep_duration;
+#[cfg(all(feature = "tokio", feature = "rand"))]
+pub use async_support::retry_async;
+#[cfg(feature = "tokio")]
+pub use async_support::{parse_timeout, smart_sleep_async, smart_sleep_async_from};
+#[cfg(feature = "cache")]
+pub use cache::clear_parse_cache;
+#[cfg(feature = "std")]
+pub use cancel::{
+    smart_sleep_cancellable, smart_sleep_cancellable_with, SleepOutcome, DEFAULT_CANCEL_POLL,
+};
+#[cfg(feature = "std")]
+pub use capped::{smart_sleep_capped, smart_sleep_capped_with, CapMode};
+#[cfg(feature = "std")]
+pub use channel::recv_timeout_smart;
+#[cfg(feature = "std")]
+pub use config::{
+    default_unit, max_sleep, reset_all_globals, set_default_unit, set_max_sleep,
+    set_sleep_granularity, set_tick_duration, set_time_scale, set_zero_sleep_yields,
+    sleep_granularity, tick_duration, time_scale, zero_sleep_yields, SCALE_ENV_VAR,
+};
+pub use core_parse::{parse_duration_core, CoreParseError};
+#[cfg(feature = "std")]
+pub use duration_parser::{
+    cumulative_schedule, extract_duration, parse_duration_prefix, parse_from_file, parse_lines,
+    parse_percent, parse_relaxed, parse_schedule, parse_sleep_duration,
+    parse_sleep_duration_nonzero, parse_sleep_duration_strict, parse_spans, recognized_units,
+    scan_units, ParsedComponent, ParsedDuration,
+};
+#[cfg(feature = "std")]
+pub use duration_utils::{
+    deadline_from_now, describe_duration_diff, durations_approx_equal, even_intervals,
+    gcd_interval, max_duration, min_duration, progress_fraction, resolve_min, snap_to_allowed,
+    validate_duration,
+};
 pub use error::{Result, SleepError};
-pub use smart_sleep::{smart_sleep, SleepInput};
+#[cfg(feature = "std")]
+pub use format::{decode_duration, encode_duration, format_duration};
+#[cfg(feature = "rand")]
+pub use jitter::{smart_sleep_jitter, smart_sleep_jitter_lenient, smart_sleep_range};
+#[cfg(feature = "std")]
+pub use locale::{parse_sleep_duration_localized, LocaleUnits};
+#[cfg(feature = "std")]
+pub use observers::{add_sleep_observer, clear_sleep_observers, SleepObserver};
+#[cfg(feature = "std")]
+pub use pacing::{do_paced, interval_for_fps, sleep_frame, EmaInterval, PollGuard};
+#[cfg(feature = "rayon")]
+pub use parallel::par_throttled_for_each;
+#[cfg(feature = "std")]
+pub use parse_options::{parse_sleep_duration_with, ParseOptions};
+#[cfg(feature = "std")]
+pub use precise::{monotonic_supported, smart_sleep_precise, PreciseSleeper};
+#[cfg(feature = "std")]
+pub use references::{clear_references, reference, register_reference, sleep_fraction_of};
+#[cfg(feature = "std")]
+pub use retry::{pow2_backoff, retry_with_backoff, Backoff};
+#[cfg(feature = "rand")]
+pub use retry::{retry, RetryConfig};
+#[cfg(feature = "serde")]
+pub use serde_support::parse_json_value;
+#[cfg(feature = "std")]
+pub use sleeper::{Sleeper, SleeperBuilder};
+#[cfg(feature = "std")]
+pub use smart_sleep::{smart_sleep, smart_sleep_report, SleepInput, SleepReport};
+#[cfg(feature = "stats")]
+pub use stats::{reset_sleep_stats, sleep_stats, CallSiteStats, SleepStats};
+pub use unit::Unit;
+#[cfg(all(windows, feature = "windows-timer"))]
+pub use windows_timer::HighResTimerGuard;
 
+#[cfg(feature = "std")]
 /// Standard sleep function for backward compatibility with `std::thread::sleep`.
 ///
 /// This function provides a simple wrapper around `std::thread::sleep` that returns
@@ -89,10 +213,39 @@ pub fn sleep(duration: Duration) -> Result<()> {
     Ok(())
 }
 
-#[cfg(test)]
+#[cfg(feature = "std")]
+/// Blocks the current thread until `deadline`.
+///
+/// This is the blocking counterpart of `tokio::time::sleep_until`: it computes the time
+/// remaining once and sleeps that long with `std::thread::sleep`, returning at once if
+/// the deadline has already passed. The remaining time is not recomputed after waking,
+/// so a spurious or early wakeup is not corrected for. Unlike [`smart_sleep`], the
+/// global time scale and granularity are not applied.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::sleep_until;
+/// use std::time::{Duration, Instant};
+///
+/// let deadline = Instant::now() + Duration::from_millis(5);
+/// sleep_until(deadline).unwrap();
+/// assert!(Instant::now() >= deadline);
+///
+/// // Past deadlines return immediately
+/// sleep_until(deadline).unwrap();
+/// ```
+pub fn sleep_until(deadline: Instant) -> Result<()> {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    if !remaining.is_zero() {
+        std::thread::sleep(remaining);
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
-    use std::time::Instant;
 
     #[test]
     fn test_basic_sleep() -> Result<()> {
@@ -102,4 +255,16 @@ mod tests {
         assert!(elapsed >= Duration::from_millis(1));
         Ok(())
     }
+
+    #[test]
+    fn test_sleep_until() -> Result<()> {
+        let start = Instant::now();
+        sleep_until(start + Duration::from_millis(20))?;
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        let start = Instant::now();
+        sleep_until(start - Duration::from_millis(20))?;
+        assert!(start.elapsed() < Duration::from_millis(20));
+        Ok(())
+    }
 }
@@ -0,0 +1,189 @@
+use crate::core_parse::{duration_from_nanos, Number};
+use crate::duration_parser::unit_multiplier;
+use crate::{Result, SleepError, Unit};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use std::time::Duration;
+
+lazy_static! {
+    static ref LOCALIZED_COMPONENT: Regex = Regex::new(r"(\d*\.?\d+)\s*(\p{L}+)").unwrap();
+}
+
+/// A table of localized unit words for [`parse_sleep_duration_localized`].
+///
+/// Words are matched case-insensitively. Words that are not registered fall back to the
+/// built-in English units, so `"1 Minute 30s"` still parses with a German table.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{parse_sleep_duration_localized, LocaleUnits, Unit};
+/// use std::time::Duration;
+///
+/// let german = LocaleUnits::new()
+///     .with_unit("sekunde", Unit::Seconds)
+///     .with_unit("sekunden", Unit::Seconds)
+///     .with_unit("minuten", Unit::Minutes);
+///
+/// let parsed = parse_sleep_duration_localized("2 Minuten 5 Sekunden", &german).unwrap();
+/// assert_eq!(parsed, Duration::from_secs(125));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LocaleUnits {
+    words: HashMap<String, Unit>,
+}
+
+impl LocaleUnits {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `word` as a spelling of `unit` and returns the table.
+    pub fn with_unit(mut self, word: &str, unit: Unit) -> Self {
+        self.insert(word, unit);
+        self
+    }
+
+    /// Registers `word` as a spelling of `unit`, replacing any previous mapping.
+    pub fn insert(&mut self, word: &str, unit: Unit) {
+        self.words.insert(word.to_lowercase(), unit);
+    }
+
+    /// Returns the unit registered for `word`, if any.
+    pub fn get(&self, word: &str) -> Option<Unit> {
+        self.words.get(&word.to_lowercase()).copied()
+    }
+
+    /// Duration of one `word`, preferring localized words over built-in units
+    fn unit_duration(&self, word: &str) -> Option<Duration> {
+        match self.words.get(word) {
+            Some(unit) => Some(unit.duration(1)),
+            None => unit_multiplier(word).map(Duration::from_nanos),
+        }
+    }
+}
+
+/// Parse a duration whose unit words come from a [`LocaleUnits`] table.
+///
+/// The input is a sequence of `number unit` components, optionally separated by
+/// whitespace or commas, such as `"1 Minute 30 Sekunden"`. Fractional values are allowed,
+/// and components are summed exactly in nanoseconds.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if a unit word is unknown, the input contains
+/// anything other than components and separators, or no component is found, and
+/// [`SleepError::NumberOutOfRange`] if the total does not fit in a [`Duration`].
+pub fn parse_sleep_duration_localized(input: &str, units: &LocaleUnits) -> Result<Duration> {
+    let lowered = input.trim().to_lowercase();
+    let mut total: u128 = 0;
+    let mut last_end = 0;
+    for caps in LOCALIZED_COMPONENT.captures_iter(&lowered) {
+        let whole = caps.get(0).map_or(0..0, |m| m.range());
+        if !is_separator(&lowered[last_end..whole.start]) {
+            return Err(SleepError::InvalidDuration(format!(
+                "Unexpected text '{}' in '{}'",
+                lowered[last_end..whole.start].trim(),
+                input
+            )));
+        }
+        last_end = whole.end;
+
+        let unit = units.unit_duration(&caps[2]).ok_or_else(|| {
+            SleepError::InvalidDuration(format!("Unknown unit '{}' in '{}'", &caps[2], input))
+        })?;
+        let value = Number::parse(&caps[1]).ok_or_else(|| {
+            SleepError::InvalidDuration(format!("Invalid value '{}' in '{}'", &caps[1], input))
+        })?;
+        total = value
+            .to_nanos(unit.as_nanos())
+            .and_then(|nanos| total.checked_add(nanos))
+            .filter(|total| *total <= Duration::MAX.as_nanos())
+            .ok_or_else(|| SleepError::NumberOutOfRange(format!("'{}' is too large", input)))?;
+    }
+
+    if last_end == 0 || !is_separator(&lowered[last_end..]) {
+        return Err(SleepError::InvalidDuration(format!(
+            "Invalid localized duration '{}'",
+            input
+        )));
+    }
+    Ok(duration_from_nanos(total))
+}
+
+/// Whether text between components only holds whitespace and commas
+fn is_separator(text: &str) -> bool {
+    text.chars().all(|c| c.is_whitespace() || c == ',')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn german() -> LocaleUnits {
+        LocaleUnits::new()
+            .with_unit("Millisekunde", Unit::Millis)
+            .with_unit("Millisekunden", Unit::Millis)
+            .with_unit("Sekunde", Unit::Seconds)
+            .with_unit("Sekunden", Unit::Seconds)
+            .with_unit("Minute", Unit::Minutes)
+            .with_unit("Minuten", Unit::Minutes)
+            .with_unit("Stunde", Unit::Hours)
+            .with_unit("Stunden", Unit::Hours)
+    }
+
+    #[test]
+    fn test_parse_german_units() -> Result<()> {
+        let units = german();
+        assert_eq!(
+            parse_sleep_duration_localized("1 Minute 30 Sekunden", &units)?,
+            Duration::from_secs(90)
+        );
+        assert_eq!(
+            parse_sleep_duration_localized("2 Stunden, 500 Millisekunden", &units)?,
+            Duration::from_millis(7_200_500)
+        );
+        assert_eq!(
+            parse_sleep_duration_localized("1.5 Sekunden", &units)?,
+            Duration::from_millis(1500)
+        );
+        assert_eq!(units.get("SEKUNDE"), Some(Unit::Seconds));
+
+        // Built-in units still work alongside localized ones
+        assert_eq!(
+            parse_sleep_duration_localized("1 Minute 5s", &units)?,
+            Duration::from_secs(65)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_localized_rejects_unknown_text() {
+        let units = german();
+        assert!(parse_sleep_duration_localized("5 Tage", &units).is_err());
+        assert!(parse_sleep_duration_localized("etwa 5 Sekunden", &units).is_err());
+        assert!(parse_sleep_duration_localized("5 Sekunden bitte", &units).is_err());
+        assert!(parse_sleep_duration_localized("", &units).is_err());
+    }
+
+    #[test]
+    fn test_parse_localized_sums_exact_nanoseconds() -> Result<()> {
+        let units = german();
+        // An f64 sum of seconds loses the nanosecond at this magnitude
+        assert_eq!(
+            parse_sleep_duration_localized("100000000 Stunden 1ns", &units)?,
+            Duration::from_secs(360_000_000_000) + Duration::from_nanos(1)
+        );
+        assert_eq!(
+            parse_sleep_duration_localized("0.1 Sekunden, 0.2 Sekunden", &units)?,
+            Duration::from_millis(300)
+        );
+        assert!(matches!(
+            parse_sleep_duration_localized("18446744073709551615 Sekunden 1 Sekunde", &units),
+            Err(SleepError::NumberOutOfRange(_))
+        ));
+        Ok(())
+    }
+}
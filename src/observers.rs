@@ -0,0 +1,65 @@
+use crate::SleepInput;
+use lazy_static::lazy_static;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// A callback registered with [`add_sleep_observer`].
+pub type SleepObserver = Box<dyn Fn(&SleepInput, Duration) + Send + Sync>;
+
+lazy_static! {
+    static ref SLEEP_OBSERVERS: RwLock<Vec<SleepObserver>> = RwLock::new(Vec::new());
+}
+
+/// Registers a callback that is invoked for every sleep before it happens.
+///
+/// Observers are called in registration order with the original input and the resolved
+/// duration (after time scale and granularity), including calls that resolve to zero and
+/// skip sleeping. Both [`smart_sleep`](crate::smart_sleep) and, with the `tokio` feature,
+/// `smart_sleep_async` notify observers. This makes it easy to log, record or assert
+/// sleeps in integration tests without touching the code under test.
+///
+/// Observers must not register or clear observers themselves.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{add_sleep_observer, clear_sleep_observers, smart_sleep};
+/// use std::sync::{Arc, Mutex};
+/// use std::time::Duration;
+///
+/// let seen = Arc::new(Mutex::new(Vec::new()));
+/// let recorder = Arc::clone(&seen);
+/// add_sleep_observer(Box::new(move |_, duration| {
+///     recorder.lock().unwrap().push(duration);
+/// }));
+///
+/// smart_sleep("1ms").unwrap();
+/// assert_eq!(*seen.lock().unwrap(), [Duration::from_millis(1)]);
+///
+/// clear_sleep_observers();
+/// ```
+pub fn add_sleep_observer(observer: SleepObserver) {
+    SLEEP_OBSERVERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(observer);
+}
+
+/// Removes every observer registered with [`add_sleep_observer`].
+pub fn clear_sleep_observers() {
+    SLEEP_OBSERVERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// Notify every registered observer of an upcoming sleep
+pub(crate) fn notify(input: &SleepInput, duration: Duration) {
+    for observer in SLEEP_OBSERVERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+    {
+        observer(input, duration);
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{smart_sleep, Result, SleepError, SleepInput};
+use std::time::{Duration, Instant};
+
+/// Returns the per-frame duration for a target frame rate.
+///
+/// This is `1s / fps`, so 60 FPS gives roughly 16.67ms.
+///
+/// # Errors
+///
+/// Returns [`SleepError::NumberOutOfRange`] if `fps` is not a positive, finite number or
+/// the resulting interval cannot be represented as a [`Duration`].
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::interval_for_fps;
+/// use std::time::Duration;
+///
+/// assert_eq!(interval_for_fps(50.0).unwrap(), Duration::from_millis(20));
+/// assert!(interval_for_fps(0.0).is_err());
+/// ```
+pub fn interval_for_fps(fps: f64) -> Result<Duration> {
+    if !(fps.is_finite() && fps > 0.0) {
+        return Err(SleepError::NumberOutOfRange(format!(
+            "Frame rate must be positive, got {}",
+            fps
+        )));
+    }
+    Duration::try_from_secs_f64(1.0 / fps).map_err(|e| {
+        SleepError::NumberOutOfRange(format!("Frame rate {} is too small: {}", fps, e))
+    })
+}
+
+/// Sleeps for one frame at the given frame rate.
+///
+/// # Notes
+///
+/// This relies on `std::thread::sleep`, whose granularity is coarse on some platforms
+/// (around 15ms on Windows), so a 60 FPS frame can easily overshoot. Prefer a spin-based
+/// precise sleep when frame timing accuracy matters.
+///
+/// # Errors
+///
+/// Returns [`SleepError::NumberOutOfRange`] if `fps` is not a positive, finite number.
+#[track_caller]
+pub fn sleep_frame(fps: f64) -> Result<()> {
+    smart_sleep(interval_for_fps(fps)?)
+}
+
+/// Runs `body` at least once, sleeping `interval` between runs while it returns `true`.
+///
+/// This is a do-while loop with pacing: the first run happens immediately, and there is
+/// no sleep after the final run. The interval is resolved once, before `body` first runs.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if `interval` cannot be parsed, in which case
+/// `body` is never run, and propagates errors from [`smart_sleep`].
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::do_paced;
+///
+/// let mut remaining = 3;
+/// do_paced("1ms", || {
+///     remaining -= 1;
+///     remaining > 0
+/// })
+/// .unwrap();
+/// assert_eq!(remaining, 0);
+/// ```
+#[track_caller]
+pub fn do_paced<F: FnMut() -> bool>(interval: impl Into<SleepInput>, mut body: F) -> Result<()> {
+    let interval = interval.into().to_duration()?;
+    while body() {
+        smart_sleep(interval)?;
+    }
+    Ok(())
+}
+
+/// An exponential moving average of observed intervals, for adaptive pacing.
+///
+/// Each [`update`](EmaInterval::update) blends a new observation into the average as
+/// `alpha * observed + (1 - alpha) * current`. Higher `alpha` values react faster to
+/// change, lower values smooth out noise.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::EmaInterval;
+/// use std::time::Duration;
+///
+/// let mut ema = EmaInterval::new(0.5, "100ms").unwrap();
+/// ema.update(Duration::from_millis(200));
+/// assert_eq!(ema.current(), Duration::from_millis(150));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct EmaInterval {
+    alpha: f64,
+    current: Duration,
+}
+
+impl EmaInterval {
+    /// Creates an average with smoothing factor `alpha` starting at `initial`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::NumberOutOfRange`] if `alpha` is not within `(0, 1]`, or
+    /// [`SleepError::InvalidDuration`] if `initial` cannot be parsed.
+    pub fn new(alpha: f64, initial: impl Into<SleepInput>) -> Result<Self> {
+        if !(alpha > 0.0 && alpha <= 1.0) {
+            return Err(SleepError::NumberOutOfRange(format!(
+                "EMA alpha must be within (0, 1], got {}",
+                alpha
+            )));
+        }
+        Ok(EmaInterval {
+            alpha,
+            current: initial.into().to_duration()?,
+        })
+    }
+
+    /// Blends an observed interval into the average.
+    pub fn update(&mut self, observed: Duration) {
+        let blended =
+            self.alpha * observed.as_secs_f64() + (1.0 - self.alpha) * self.current.as_secs_f64();
+        self.current = Duration::try_from_secs_f64(blended).unwrap_or(Duration::MAX);
+    }
+
+    /// Returns the current average interval.
+    pub fn current(&self) -> Duration {
+        self.current
+    }
+
+    /// Sleeps for the current average interval and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from [`smart_sleep`].
+    #[track_caller]
+    pub fn sleep(&self) -> Result<Duration> {
+        smart_sleep(self.current)?;
+        Ok(self.current)
+    }
+}
+
+/// Paces a polling loop and enforces a total time budget.
+///
+/// Each [`next`](PollGuard::next) call sleeps for the interval, clamped to the time left
+/// in the budget, and returns `true`. Once the budget is used up it returns `false`
+/// without sleeping, which ends a `while guard.next()? { ... }` loop.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::PollGuard;
+/// use std::time::Duration;
+///
+/// let mut guard = PollGuard::new("5ms", Duration::from_millis(12)).unwrap();
+/// let mut polls = 0;
+/// while guard.next().unwrap() {
+///     polls += 1;
+/// }
+/// assert!(polls <= 3);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PollGuard {
+    interval: Duration,
+    deadline: Instant,
+}
+
+impl PollGuard {
+    /// Creates a guard that sleeps `interval` per poll for at most `total` overall.
+    ///
+    /// The budget starts counting immediately.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::InvalidDuration`] if `interval` cannot be parsed.
+    pub fn new(interval: impl Into<SleepInput>, total: Duration) -> Result<Self> {
+        Ok(PollGuard {
+            interval: interval.into().to_duration()?,
+            deadline: crate::deadline_from_now(total)?,
+        })
+    }
+
+    /// Returns the time left in the budget.
+    pub fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+
+    /// Sleeps for the next interval and returns whether the budget allowed it.
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from [`smart_sleep`].
+    #[allow(clippy::should_implement_trait)]
+    #[track_caller]
+    pub fn next(&mut self) -> Result<bool> {
+        let remaining = self.remaining();
+        if remaining.is_zero() {
+            return Ok(false);
+        }
+        smart_sleep(self.interval.min(remaining))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_for_fps() -> Result<()> {
+        assert_eq!(interval_for_fps(60.0)?, Duration::from_nanos(16_666_667));
+        assert_eq!(interval_for_fps(30.0)?, Duration::from_nanos(33_333_333));
+
+        for fps in [0.0, -30.0, f64::NAN, f64::INFINITY, 1e-300] {
+            assert!(matches!(
+                interval_for_fps(fps),
+                Err(SleepError::NumberOutOfRange(_))
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_sleep_frame() -> Result<()> {
+        let start = Instant::now();
+        sleep_frame(60.0)?;
+        assert!(start.elapsed() >= Duration::from_millis(16));
+
+        assert!(sleep_frame(0.0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_ema_interval_converges() -> Result<()> {
+        let mut ema = EmaInterval::new(0.3, "100ms")?;
+        for _ in 0..50 {
+            ema.update(Duration::from_millis(20));
+        }
+        let diff = ema.current().abs_diff(Duration::from_millis(20));
+        assert!(diff < Duration::from_micros(10), "{:?}", ema.current());
+
+        let start = Instant::now();
+        assert_eq!(ema.sleep()?, ema.current());
+        assert!(start.elapsed() >= Duration::from_millis(19));
+
+        for alpha in [0.0, -0.5, 1.5, f64::NAN] {
+            assert!(EmaInterval::new(alpha, "1s").is_err());
+        }
+        assert!(EmaInterval::new(1.0, "1s").is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_do_paced_runs_at_least_once() -> Result<()> {
+        let start = Instant::now();
+        let mut runs = Vec::new();
+        do_paced("20ms", || {
+            runs.push(start.elapsed());
+            runs.len() < 3
+        })?;
+
+        // Three runs with two sleeps between them
+        assert_eq!(runs.len(), 3);
+        assert!(runs[0] < Duration::from_millis(20));
+        assert!(runs[2] >= Duration::from_millis(40));
+        assert!(start.elapsed() < Duration::from_millis(60 + 50));
+
+        let mut ran = false;
+        assert!(do_paced("bogus", || {
+            ran = true;
+            false
+        })
+        .is_err());
+        assert!(!ran);
+        Ok(())
+    }
+
+    #[test]
+    fn test_poll_guard_enforces_total() -> Result<()> {
+        let start = Instant::now();
+        let mut guard = PollGuard::new("50ms", Duration::from_millis(150))?;
+        let mut sleeps = 0;
+        while guard.next()? {
+            sleeps += 1;
+        }
+        let elapsed = start.elapsed();
+
+        // About three sleeps; the last one is clamped to the remaining budget
+        assert!((2..=3).contains(&sleeps), "{}", sleeps);
+        assert!(elapsed >= Duration::from_millis(150));
+        assert!(elapsed < Duration::from_millis(250), "{:?}", elapsed);
+        assert_eq!(guard.remaining(), Duration::ZERO);
+        assert!(!guard.next()?);
+
+        assert!(PollGuard::new("bogus", Duration::from_secs(1)).is_err());
+        Ok(())
+    }
+}
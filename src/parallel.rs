@@ -0,0 +1,114 @@
+use crate::smart_sleep::run_sleep;
+use crate::{Result, SleepError, SleepInput};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::panic::Location;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Runs `f` on every item in parallel while spacing out task starts by `interval`.
+///
+/// Workers share a single "next start" time guarded by a mutex: each task reserves the
+/// next free slot, sleeps until it, and then runs. No two tasks start closer together
+/// than `interval`, even across rayon worker threads, which prevents bursts of
+/// simultaneous external calls. Tasks themselves may still overlap.
+///
+/// Each wait goes through the same pipeline as [`smart_sleep`](crate::smart_sleep), so the
+/// global time scale, `max_sleep`, observers and statistics apply to it. The slots are
+/// reserved in real time, so a time scale shortens or lengthens the waits but not the
+/// spacing of later slots.
+///
+/// Only available with the `rayon` feature.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if `interval` cannot be parsed, and
+/// [`SleepError::NumberOutOfRange`] if the first slot after now cannot be represented as
+/// an [`Instant`]; no items are processed in either case. Errors from a wait, such as
+/// exceeding `max_sleep` or running out of representable slots, stop the iteration and
+/// are returned; items that have not started yet are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::par_throttled_for_each;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// let processed = AtomicUsize::new(0);
+/// par_throttled_for_each(0..4, "1ms", |_| {
+///     processed.fetch_add(1, Ordering::SeqCst);
+/// })
+/// .unwrap();
+/// assert_eq!(processed.load(Ordering::SeqCst), 4);
+/// ```
+#[track_caller]
+pub fn par_throttled_for_each<I, S, F>(items: I, interval: S, f: F) -> Result<()>
+where
+    I: IntoParallelIterator,
+    S: Into<SleepInput>,
+    F: Fn(I::Item) + Sync + Send,
+{
+    let caller = Location::caller();
+    let interval = interval.into().to_duration()?;
+    let now = Instant::now();
+    now.checked_add(interval)
+        .ok_or_else(|| slot_out_of_range(interval))?;
+    let next_start = Mutex::new(now);
+
+    items.into_par_iter().try_for_each(|item| {
+        let slot = {
+            let mut next = next_start.lock().unwrap_or_else(|e| e.into_inner());
+            let slot = (*next).max(Instant::now());
+            *next = slot
+                .checked_add(interval)
+                .ok_or_else(|| slot_out_of_range(interval))?;
+            slot
+        };
+        run_sleep(&SleepInput::Until(slot), caller, std::thread::sleep)?;
+        f(item);
+        Ok(())
+    })
+}
+
+/// Error for an interval that pushes the next start past the range of [`Instant`]
+fn slot_out_of_range(interval: Duration) -> SleepError {
+    SleepError::NumberOutOfRange(format!(
+        "Throttle interval {:?} is too large to schedule",
+        interval
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_starts_are_spaced() -> Result<()> {
+        let starts = Mutex::new(Vec::new());
+        par_throttled_for_each(0..6, "20ms", |_| {
+            starts.lock().unwrap().push(Instant::now());
+            std::thread::sleep(Duration::from_millis(5));
+        })?;
+
+        let mut starts = starts.into_inner().unwrap();
+        starts.sort();
+        assert_eq!(starts.len(), 6);
+        for pair in starts.windows(2) {
+            assert!(pair[1] - pair[0] >= Duration::from_millis(19), "{:?}", pair);
+        }
+
+        assert!(par_throttled_for_each(0..1, "bogus", |_: i32| {}).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_oversized_interval_is_rejected_up_front() {
+        for interval in [SleepInput::Duration(Duration::MAX), "1e19s".into()] {
+            let ran = Mutex::new(0);
+            let result = par_throttled_for_each(0..3, interval, |_| {
+                *ran.lock().unwrap() += 1;
+            });
+            assert!(matches!(result, Err(SleepError::NumberOutOfRange(_))));
+            assert_eq!(*ran.lock().unwrap(), 0);
+        }
+    }
+}
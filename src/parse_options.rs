@@ -0,0 +1,384 @@
+use crate::{parse_sleep_duration, Result, SleepError, Unit};
+use std::time::Duration;
+
+/// Opt-in extensions to the duration grammar accepted by [`parse_sleep_duration`].
+///
+/// All options are off by default, in which case [`parse_sleep_duration_with`] behaves
+/// exactly like [`parse_sleep_duration`].
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{parse_sleep_duration_with, ParseOptions};
+/// use std::time::Duration;
+///
+/// let options = ParseOptions {
+///     allow_arithmetic: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     parse_sleep_duration_with("1m-10s", &options).unwrap(),
+///     Duration::from_secs(50)
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Treat `-` and `+` between components as subtraction and addition.
+    ///
+    /// `"1m-10s"` resolves to 50 seconds. The total is floored at zero. This is off by
+    /// default because it conflicts with the negative-value semantics of plain input.
+    pub allow_arithmetic: bool,
+
+    /// Accept unit words that stand for a single unit.
+    ///
+    /// A unit word on its own or after an article implies a quantity of one, so
+    /// `"second"` and `"a second"` resolve to 1 second and `"an hour"` to 1 hour. Inputs
+    /// containing digits are parsed as usual. The word `"plus"` joins components that are
+    /// added together, so `"1 minute plus 30 seconds"` and `"a minute plus a second"`
+    /// resolve to 90 and 61 seconds.
+    pub allow_words: bool,
+
+    /// Bound totals to the range of a unit.
+    ///
+    /// The ceiling is one of the next larger unit, and totals equal to it are accepted:
+    ///
+    /// | `max_unit`        | Largest accepted total |
+    /// |-------------------|------------------------|
+    /// | [`Unit::Millis`]  | 1 second               |
+    /// | [`Unit::Seconds`] | 1 minute               |
+    /// | [`Unit::Minutes`] | 1 hour                 |
+    /// | [`Unit::Hours`]   | 1 day                  |
+    ///
+    /// The bound applies to the total, so components in any unit count toward it,
+    /// including nanoseconds, microseconds, days and weeks. Those units have no [`Unit`]
+    /// variant and cannot be used as a ceiling themselves. What happens to larger totals
+    /// is controlled by [`clamp_to_max_unit`](Self::clamp_to_max_unit).
+    pub max_unit: Option<Unit>,
+
+    /// Cap totals above the [`max_unit`](Self::max_unit) range instead of rejecting them.
+    pub clamp_to_max_unit: bool,
+}
+
+/// Parse sleep duration with the given [`ParseOptions`]
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if the input (or any arithmetic operand)
+/// cannot be parsed, or [`SleepError::NumberOutOfRange`] if the total exceeds the
+/// [`ParseOptions::max_unit`] range and clamping is off.
+pub fn parse_sleep_duration_with(input: &str, options: &ParseOptions) -> Result<Duration> {
+    let duration = parse_unbounded(input, options)?;
+    match options.max_unit {
+        Some(unit) if duration > unit_range(unit) => {
+            if options.clamp_to_max_unit {
+                Ok(unit_range(unit))
+            } else {
+                Err(SleepError::NumberOutOfRange(format!(
+                    "'{}' exceeds the {:?} range of {:?}",
+                    input,
+                    unit,
+                    unit_range(unit)
+                )))
+            }
+        }
+        _ => Ok(duration),
+    }
+}
+
+/// Parse with every grammar option applied, before any range bound
+fn parse_unbounded(input: &str, options: &ParseOptions) -> Result<Duration> {
+    if options.allow_words {
+        if let Some(duration) = parse_unit_word(input) {
+            return Ok(duration);
+        }
+        if let Some(operands) = split_plus_words(input) {
+            return operands.iter().try_fold(Duration::ZERO, |total, operand| {
+                if operand.is_empty() {
+                    return Err(SleepError::InvalidDuration(format!(
+                        "Missing operand around 'plus' in '{}'",
+                        input
+                    )));
+                }
+                Ok(total.saturating_add(parse_unbounded(operand, options)?))
+            });
+        }
+    }
+    if options.allow_arithmetic && input.trim().contains(['-', '+']) {
+        return parse_arithmetic(input.trim());
+    }
+    parse_sleep_duration(input)
+}
+
+/// The largest total within the range of `unit`: one of the next larger unit, inclusive
+fn unit_range(unit: Unit) -> Duration {
+    match unit {
+        Unit::Millis => Duration::from_secs(1),
+        Unit::Seconds => Duration::from_secs(60),
+        Unit::Minutes => Duration::from_secs(3600),
+        Unit::Hours => Duration::from_secs(86_400),
+    }
+}
+
+/// Parse a bare unit word, optionally preceded by an article, as one of that unit
+fn parse_unit_word(input: &str) -> Option<Duration> {
+    let lowered = input.trim().to_lowercase();
+    let word = ["a ", "an ", "one "]
+        .iter()
+        .find_map(|article| lowered.strip_prefix(article))
+        .unwrap_or(&lowered)
+        .trim();
+    if !word.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    crate::duration_parser::unit_multiplier(word).map(Duration::from_nanos)
+}
+
+/// Split the input at standalone `"plus"` words, or `None` if it contains none
+fn split_plus_words(input: &str) -> Option<Vec<String>> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    if !words.iter().any(|word| word.eq_ignore_ascii_case("plus")) {
+        return None;
+    }
+    Some(
+        words
+            .split(|word| word.eq_ignore_ascii_case("plus"))
+            .map(|operand| operand.join(" "))
+            .collect(),
+    )
+}
+
+/// Sum the `+`/`-` separated components of an expression, flooring at zero
+fn parse_arithmetic(input: &str) -> Result<Duration> {
+    let mut total: i128 = 0;
+    let mut sign: i128 = 1;
+    let mut start = 0;
+
+    let operators = input
+        .match_indices(['-', '+'])
+        .filter(|&(index, _)| !is_exponent_sign(input, index));
+    for (index, op) in operators.chain([(input.len(), "")]) {
+        let operand = input[start..index].trim();
+        if operand.is_empty() {
+            // Only a leading sign may appear without a left-hand operand
+            if start != 0 || index == input.len() {
+                return Err(SleepError::InvalidDuration(format!(
+                    "Missing operand in duration expression: '{}'",
+                    input
+                )));
+            }
+        } else {
+            total += sign * parse_sleep_duration(operand)?.as_nanos() as i128;
+        }
+        sign = if op == "-" { -1 } else { 1 };
+        start = index + op.len();
+    }
+
+    let nanos = total.max(0) as u128;
+    Ok(crate::core_parse::duration_from_nanos(nanos))
+}
+
+/// Check whether the sign at `index` belongs to an exponent, as in `"1.5e-1s"`
+fn is_exponent_sign(input: &str, index: usize) -> bool {
+    let bytes = input.as_bytes();
+    index >= 2
+        && matches!(bytes[index - 1], b'e' | b'E')
+        && bytes[index - 2].is_ascii_digit()
+        && bytes.get(index + 1).is_some_and(u8::is_ascii_digit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arithmetic() -> ParseOptions {
+        ParseOptions {
+            allow_arithmetic: true,
+            ..Default::default()
+        }
+    }
+
+    fn words() -> ParseOptions {
+        ParseOptions {
+            allow_words: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() -> Result<()> {
+        let options = arithmetic();
+        assert_eq!(
+            parse_sleep_duration_with("1m-10s", &options)?,
+            Duration::from_secs(50)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("1h - 30m + 5s", &options)?,
+            Duration::from_secs(1805)
+        );
+
+        // Floors at zero
+        assert_eq!(
+            parse_sleep_duration_with("10s-1m", &options)?,
+            Duration::ZERO
+        );
+        assert_eq!(parse_sleep_duration_with("-5s", &options)?, Duration::ZERO);
+
+        // Exponent signs are part of the number, not operators
+        assert_eq!(
+            parse_sleep_duration_with("1.5e-1s + 1s", &options)?,
+            Duration::from_millis(1150)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("1e3ms-2E+2ms", &options)?,
+            Duration::from_millis(800)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("1e-3", &options)?,
+            Duration::from_micros(1)
+        );
+
+        assert!(parse_sleep_duration_with("1m--10s", &options).is_err());
+        assert!(parse_sleep_duration_with("1m-", &options).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_arithmetic_disabled_by_default() -> Result<()> {
+        let options = ParseOptions::default();
+        assert_eq!(parse_sleep_duration_with("-5", &options)?, Duration::ZERO);
+        assert_eq!(
+            parse_sleep_duration_with("1m30s", &options)?,
+            Duration::from_secs(90)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bare_unit_words() -> Result<()> {
+        let options = words();
+        assert_eq!(
+            parse_sleep_duration_with("second", &options)?,
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("Minute", &options)?,
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("a second", &options)?,
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("an hour", &options)?,
+            Duration::from_secs(3600)
+        );
+
+        // Numeric input is unaffected
+        assert_eq!(
+            parse_sleep_duration_with("2 minutes", &options)?,
+            Duration::from_secs(120)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("500", &options)?,
+            Duration::from_millis(500)
+        );
+
+        assert!(parse_sleep_duration_with("a moment", &options).is_err());
+        assert!(parse_sleep_duration_with("second", &ParseOptions::default()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_plus_words() -> Result<()> {
+        let options = words();
+        assert_eq!(
+            parse_sleep_duration_with("1 minute plus 30 seconds", &options)?,
+            Duration::from_secs(90)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("1h PLUS 2m plus 3s plus 500ms", &options)?,
+            Duration::from_millis(3_723_500)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("a minute plus a second", &options)?,
+            Duration::from_secs(61)
+        );
+
+        // "plus" and "+" can be mixed when arithmetic is enabled too
+        let both = ParseOptions {
+            allow_arithmetic: true,
+            ..words()
+        };
+        assert_eq!(
+            parse_sleep_duration_with("1m-10s plus 5s+5s", &both)?,
+            Duration::from_secs(60)
+        );
+
+        for input in ["plus 5s", "5s plus", "5s plus plus 5s"] {
+            assert!(
+                parse_sleep_duration_with(input, &options).is_err(),
+                "{}",
+                input
+            );
+        }
+        assert!(parse_sleep_duration_with("1m plus 30s", &ParseOptions::default()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_unit_ceiling() -> Result<()> {
+        let mut options = ParseOptions {
+            max_unit: Some(Unit::Minutes),
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_sleep_duration_with("45m", &options)?,
+            Duration::from_secs(45 * 60)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("1h", &options)?,
+            Duration::from_secs(3600)
+        );
+        assert!(matches!(
+            parse_sleep_duration_with("500m", &options),
+            Err(SleepError::NumberOutOfRange(_))
+        ));
+
+        options.clamp_to_max_unit = true;
+        assert_eq!(
+            parse_sleep_duration_with("500m", &options)?,
+            Duration::from_secs(3600)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("30s", &options)?,
+            Duration::from_secs(30)
+        );
+
+        options.max_unit = Some(Unit::Hours);
+        assert_eq!(
+            parse_sleep_duration_with("500m", &options)?,
+            Duration::from_secs(500 * 60)
+        );
+        assert_eq!(
+            parse_sleep_duration_with("2d", &options)?,
+            Duration::from_secs(86_400)
+        );
+
+        // Units without a Unit variant count toward the total like any other
+        options.clamp_to_max_unit = false;
+        assert_eq!(
+            parse_sleep_duration_with("1d", &options)?,
+            Duration::from_secs(86_400)
+        );
+        assert!(parse_sleep_duration_with("1d 1ns", &options).is_err());
+        assert!(parse_sleep_duration_with("1w", &options).is_err());
+
+        options.max_unit = Some(Unit::Millis);
+        assert_eq!(
+            parse_sleep_duration_with("999ms 1000us", &options)?,
+            Duration::from_secs(1)
+        );
+        assert!(parse_sleep_duration_with("1s 1us", &options).is_err());
+        Ok(())
+    }
+}
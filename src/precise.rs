@@ -0,0 +1,244 @@
+use crate::smart_sleep::run_sleep;
+use crate::{Result, SleepInput};
+use lazy_static::lazy_static;
+use std::panic::Location;
+use std::time::{Duration, Instant};
+
+/// Default spin threshold of [`PreciseSleeper`]
+const DEFAULT_SPIN_THRESHOLD: Duration = Duration::from_millis(2);
+
+/// Number of back-to-back clock reads taken by the monotonic probe
+const MONOTONIC_PROBES: usize = 64;
+
+lazy_static! {
+    static ref MONOTONIC: bool = probe_monotonic();
+}
+
+/// Returns whether [`Instant::now`] appears to be monotonic on this platform.
+///
+/// The clock is probed once per process by checking that consecutive readings never go
+/// backwards. Deadline-driven helpers such as [`PreciseSleeper`] consult this and fall
+/// back to a single `std::thread::sleep` of the resolved duration when it returns
+/// `false`, trading precision for a sleep that can't get stuck on a broken clock.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::monotonic_supported;
+///
+/// // True on every tier-1 platform
+/// assert!(monotonic_supported());
+/// ```
+pub fn monotonic_supported() -> bool {
+    *MONOTONIC
+}
+
+fn probe_monotonic() -> bool {
+    let mut previous = Instant::now();
+    for _ in 0..MONOTONIC_PROBES {
+        let now = Instant::now();
+        if now < previous {
+            return false;
+        }
+        previous = now;
+    }
+    true
+}
+
+/// A sleeper that trades CPU time for accuracy on short sleeps.
+///
+/// The OS scheduler often overshoots short sleeps by a full tick (around 15ms on
+/// Windows). `PreciseSleeper` hands the bulk of a sleep to `std::thread::sleep` and
+/// busy-spins for the final `spin_threshold`, polling [`Instant`] with
+/// [`std::hint::spin_loop`]. A larger threshold is more accurate but burns more CPU;
+/// a zero threshold never spins.
+///
+/// On Windows with the `windows-timer` feature, `with_high_res_timer` additionally raises
+/// the system timer resolution while the thread sleeps, so less of each sleep needs spinning.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::PreciseSleeper;
+/// use std::time::{Duration, Instant};
+///
+/// let sleeper = PreciseSleeper::new().with_spin_threshold(Duration::from_millis(1));
+/// let start = Instant::now();
+/// sleeper.sleep("3ms").unwrap();
+/// assert!(start.elapsed() >= Duration::from_millis(3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreciseSleeper {
+    spin_threshold: Duration,
+    #[cfg(all(windows, feature = "windows-timer"))]
+    high_res_timer: bool,
+}
+
+impl Default for PreciseSleeper {
+    fn default() -> Self {
+        PreciseSleeper {
+            spin_threshold: DEFAULT_SPIN_THRESHOLD,
+            #[cfg(all(windows, feature = "windows-timer"))]
+            high_res_timer: false,
+        }
+    }
+}
+
+impl PreciseSleeper {
+    /// Creates a sleeper with the default spin threshold of 2ms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how much of the end of each sleep is busy-spun instead of slept.
+    pub fn with_spin_threshold(mut self, threshold: Duration) -> Self {
+        self.spin_threshold = threshold;
+        self
+    }
+
+    /// Returns the configured spin threshold.
+    pub fn spin_threshold(&self) -> Duration {
+        self.spin_threshold
+    }
+
+    /// Holds a [`HighResTimerGuard`](crate::HighResTimerGuard) while the thread sleeps.
+    ///
+    /// The guard raises the timer resolution to 1ms for the `std::thread::sleep` phase
+    /// of each sleep and restores it before spinning. If the system rejects the request,
+    /// the sleep proceeds at the default resolution.
+    ///
+    /// Only available on Windows with the `windows-timer` feature.
+    #[cfg(all(windows, feature = "windows-timer"))]
+    pub fn with_high_res_timer(mut self, enabled: bool) -> Self {
+        self.high_res_timer = enabled;
+        self
+    }
+
+    /// Sleeps for the input, spinning through the final stretch.
+    ///
+    /// Inputs are resolved exactly like [`smart_sleep`](crate::smart_sleep), including
+    /// the global time scale and granularity, and zero or negative inputs return at once
+    /// (or yield, with [`set_zero_sleep_yields`](crate::set_zero_sleep_yields)).
+    /// If [`monotonic_supported`] reports a broken clock, the whole duration is handed to
+    /// `std::thread::sleep` without spinning.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::InvalidDuration`](crate::SleepError::InvalidDuration) if the
+    /// input string cannot be parsed.
+    #[track_caller]
+    pub fn sleep<S: Into<SleepInput>>(&self, input: S) -> Result<()> {
+        run_sleep(&input.into(), Location::caller(), |duration| {
+            self.spin_sleep(duration)
+        })?;
+        Ok(())
+    }
+
+    /// Sleep through all but the spin threshold, then spin until `duration` has passed
+    fn spin_sleep(&self, duration: Duration) {
+        if !monotonic_supported() {
+            std::thread::sleep(duration);
+            return;
+        }
+
+        let start = Instant::now();
+        if duration > self.spin_threshold {
+            #[cfg(all(windows, feature = "windows-timer"))]
+            let _timer = self
+                .high_res_timer
+                .then(crate::HighResTimerGuard::new)
+                .and_then(|guard| guard.ok());
+            std::thread::sleep(duration - self.spin_threshold);
+        }
+        while start.elapsed() < duration {
+            std::hint::spin_loop();
+        }
+    }
+}
+
+/// Sleeps with a [`PreciseSleeper`] using the default 2ms spin threshold.
+///
+/// Build a [`PreciseSleeper`] directly to change the threshold or, on Windows, to opt
+/// into a high-resolution timer for the sleep phase.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`](crate::SleepError::InvalidDuration) if the
+/// input string cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::smart_sleep_precise;
+///
+/// smart_sleep_precise("500us").unwrap();
+/// smart_sleep_precise(0).unwrap();
+/// ```
+#[track_caller]
+pub fn smart_sleep_precise<S: Into<SleepInput>>(input: S) -> Result<()> {
+    PreciseSleeper::new().sleep(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precise_short_sleep() -> Result<()> {
+        for _ in 0..5 {
+            let start = Instant::now();
+            smart_sleep_precise("500us")?;
+            let elapsed = start.elapsed();
+            assert!(elapsed >= Duration::from_micros(500));
+            // Spinning keeps short sleeps well clear of a scheduler tick
+            assert!(elapsed < Duration::from_millis(10), "{:?}", elapsed);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_precise_sleeper_threshold() -> Result<()> {
+        let sleeper = PreciseSleeper::new();
+        assert_eq!(sleeper.spin_threshold(), Duration::from_millis(2));
+
+        let sleeper = sleeper.with_spin_threshold(Duration::ZERO);
+        let start = Instant::now();
+        sleeper.sleep("5ms")?;
+        assert!(start.elapsed() >= Duration::from_millis(5));
+
+        let start = Instant::now();
+        sleeper.sleep(-1)?;
+        assert!(start.elapsed() < Duration::from_millis(5));
+
+        assert!(sleeper.sleep("bogus").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_monotonic_probe() -> Result<()> {
+        // The normal path: the clock is sane, so the sleeper spins to the deadline.
+        // On a broken clock the sleeper would fall back to one thread::sleep instead.
+        assert!(probe_monotonic());
+        assert!(monotonic_supported());
+
+        let start = Instant::now();
+        smart_sleep_precise("1ms")?;
+        assert!(start.elapsed() >= Duration::from_millis(1));
+        Ok(())
+    }
+
+    #[cfg(all(windows, feature = "windows-timer"))]
+    #[test]
+    fn test_precise_sleeper_with_high_res_timer() -> Result<()> {
+        let sleeper = PreciseSleeper::new()
+            .with_spin_threshold(Duration::ZERO)
+            .with_high_res_timer(true);
+        let start = Instant::now();
+        for _ in 0..10 {
+            sleeper.sleep("1ms")?;
+        }
+        // Without the raised resolution ten 1ms sleeps typically take ~150ms
+        assert!(start.elapsed() < Duration::from_millis(80));
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{smart_sleep, Result, SleepError};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+lazy_static! {
+    static ref REFERENCES: RwLock<HashMap<String, Duration>> = RwLock::new(HashMap::new());
+}
+
+/// Registers a named reference duration, replacing any previous one with that name.
+///
+/// References let modules share a time budget by name and sleep fractions of it with
+/// [`sleep_fraction_of`], so retuning the budget in one place rescales every sleep.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{register_reference, sleep_fraction_of};
+/// use std::time::Duration;
+///
+/// register_reference("poll_budget", Duration::from_millis(10));
+/// assert_eq!(sleep_fraction_of("poll_budget", 0.1).unwrap(), Duration::from_millis(1));
+/// ```
+pub fn register_reference(name: impl Into<String>, duration: Duration) {
+    REFERENCES
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(name.into(), duration);
+}
+
+/// Returns the duration registered under `name`, if any.
+pub fn reference(name: &str) -> Option<Duration> {
+    REFERENCES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(name)
+        .copied()
+}
+
+/// Removes every reference registered with [`register_reference`].
+pub fn clear_references() {
+    REFERENCES
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// Sleeps for `fraction` of the reference registered under `name` and returns that time.
+///
+/// The sleep goes through [`smart_sleep`], so the global time scale and granularity
+/// apply; the returned duration is the unscaled `reference * fraction`.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if no reference is registered under `name`,
+/// or [`SleepError::NumberOutOfRange`] if `fraction` is negative, not finite, or yields
+/// a duration that cannot be represented.
+#[track_caller]
+pub fn sleep_fraction_of(name: &str, fraction: f64) -> Result<Duration> {
+    let base = reference(name).ok_or_else(|| {
+        SleepError::InvalidDuration(format!("No reference duration registered as '{}'", name))
+    })?;
+    if !(fraction.is_finite() && fraction >= 0.0) {
+        return Err(SleepError::NumberOutOfRange(format!(
+            "Fraction must be a finite, non-negative number, got {}",
+            fraction
+        )));
+    }
+    let duration = Duration::try_from_secs_f64(base.as_secs_f64() * fraction)
+        .map_err(|e| SleepError::NumberOutOfRange(format!("{} of '{}': {}", fraction, name, e)))?;
+    smart_sleep(duration)?;
+    Ok(duration)
+}
@@ -0,0 +1,557 @@
+use crate::core_parse::duration_from_nanos;
+use crate::{smart_sleep, Result, SleepError, SleepInput};
+use std::time::Duration;
+
+/// An exponential backoff schedule.
+///
+/// Each call to [`Backoff::next_delay`] returns `base * factor^n` for the `n`-th delay,
+/// capped at an optional maximum. `Backoff` is also an infinite [`Iterator`] over the
+/// same schedule.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::Backoff;
+/// use std::time::Duration;
+///
+/// let delays: Vec<Duration> = Backoff::new("100ms", 2.0)
+///     .unwrap()
+///     .with_max(Duration::from_millis(300))
+///     .take(4)
+///     .collect();
+/// assert_eq!(
+///     delays,
+///     [100, 200, 300, 300].map(Duration::from_millis)
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    base: Duration,
+    factor: f64,
+    max: Option<Duration>,
+    attempt: u32,
+}
+
+impl Backoff {
+    /// Creates a backoff schedule starting at `base` and growing by `factor` each step.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::InvalidDuration`] if `base` cannot be parsed, or
+    /// [`SleepError::NumberOutOfRange`] if `factor` is not a finite number of at least 1.0.
+    pub fn new<S: Into<SleepInput>>(base: S, factor: f64) -> Result<Self> {
+        if !(factor.is_finite() && factor >= 1.0) {
+            return Err(SleepError::NumberOutOfRange(format!(
+                "Backoff factor must be a finite number >= 1.0, got {}",
+                factor
+            )));
+        }
+        Ok(Backoff {
+            base: base.into().to_duration()?,
+            factor,
+            max: None,
+            attempt: 0,
+        })
+    }
+
+    /// Caps every delay at `max`.
+    pub fn with_max(mut self, max: Duration) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Returns the next delay in the schedule and advances it.
+    pub fn next_delay(&mut self) -> Duration {
+        // 0 * factor^n turns into NaN once factor^n overflows to infinity
+        if self.base.is_zero() {
+            self.attempt = self.attempt.saturating_add(1);
+            return Duration::ZERO;
+        }
+        let exponent = i32::try_from(self.attempt).unwrap_or(i32::MAX);
+        let scaled = self.base.as_secs_f64() * self.factor.powi(exponent);
+        let delay = Duration::try_from_secs_f64(scaled).unwrap_or(Duration::MAX);
+        self.attempt = self.attempt.saturating_add(1);
+
+        match self.max {
+            Some(max) => delay.min(max),
+            None => delay,
+        }
+    }
+
+    /// Restarts the schedule from the base delay.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+impl Iterator for Backoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        Some(self.next_delay())
+    }
+}
+
+/// Returns the `attempt`-th delay of a doubling schedule, `base << attempt`, capped at `max`.
+///
+/// Unlike [`Backoff`], this doubles by shifting the nanosecond count of `base`, so delays
+/// are exact and don't pick up float rounding, including sub-millisecond parts of `base`.
+/// Shifts that would overflow saturate to `max`.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if `base` cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::pow2_backoff;
+/// use std::time::Duration;
+///
+/// let max = Duration::from_secs(1);
+/// assert_eq!(pow2_backoff("100ms", 2, max).unwrap(), Duration::from_millis(400));
+/// assert_eq!(pow2_backoff("100ms", 10, max).unwrap(), max);
+/// ```
+pub fn pow2_backoff<S: Into<SleepInput>>(base: S, attempt: u32, max: Duration) -> Result<Duration> {
+    let nanos = base.into().to_duration()?.as_nanos();
+    let shifted = if attempt < u128::BITS && nanos <= u128::MAX >> attempt {
+        nanos << attempt
+    } else if nanos == 0 {
+        0
+    } else {
+        u128::MAX
+    };
+    Ok(duration_from_nanos(shifted.min(max.as_nanos())))
+}
+
+/// Runs `op` up to `attempts` times, sleeping `base * factor^n` between failures.
+///
+/// The delays follow a [`Backoff`] schedule, optionally capped at `max`, and are slept
+/// with [`smart_sleep`], so the global time scale applies. `base` is resolved once up
+/// front. Unlike `retry` from the `rand` feature, this adds no jitter.
+///
+/// The outer [`Result`] reports invalid arguments; the inner one is the first success
+/// of `op`, or its last error once every attempt has failed.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if `base` cannot be parsed, or
+/// [`SleepError::NumberOutOfRange`] if `attempts` is zero or `factor` is not a finite
+/// number of at least 1.0. In both cases `op` is never run.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::retry_with_backoff;
+///
+/// let mut calls = 0;
+/// let result = retry_with_backoff(5, "1ms", 2.0, None, || {
+///     calls += 1;
+///     if calls < 3 { Err("not yet") } else { Ok(calls) }
+/// });
+/// assert_eq!(result.unwrap(), Ok(3));
+/// ```
+#[track_caller]
+pub fn retry_with_backoff<T, E, F>(
+    attempts: usize,
+    base: impl Into<SleepInput>,
+    factor: f64,
+    max: Option<Duration>,
+    mut op: F,
+) -> Result<std::result::Result<T, E>>
+where
+    F: FnMut() -> std::result::Result<T, E>,
+{
+    if attempts == 0 {
+        return Err(SleepError::NumberOutOfRange(
+            "Retry needs at least one attempt".to_string(),
+        ));
+    }
+    let mut backoff = Backoff::new(base, factor)?;
+    backoff.max = max;
+
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Ok(value) => return Ok(Ok(value)),
+            Err(e) if attempt >= attempts => return Ok(Err(e)),
+            Err(_) => {
+                smart_sleep(backoff.next_delay())?;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Configuration for [`retry`]: a capped, jittered exponential backoff policy.
+///
+/// Every setting is checked when it is made, so a `RetryConfig` always describes a usable
+/// policy and [`retry`] cannot fail because of it. Only available with the `rand` feature.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::RetryConfig;
+/// use std::time::Duration;
+///
+/// let config = RetryConfig::new("100ms")
+///     .unwrap()
+///     .with_max_attempts(5)
+///     .unwrap()
+///     .with_jitter(0.2)
+///     .unwrap()
+///     .with_max_delay(Duration::from_secs(2));
+/// assert_eq!(config.base(), Duration::from_millis(100));
+/// assert!(RetryConfig::new("100ms").unwrap().with_factor(0.5).is_err());
+/// ```
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    base: Duration,
+    factor: f64,
+    max_attempts: usize,
+    max_delay: Duration,
+    jitter: f64,
+}
+
+#[cfg(feature = "rand")]
+impl RetryConfig {
+    /// Creates a configuration with the given base delay.
+    ///
+    /// Defaults to a factor of 2.0, 3 attempts, a 30 second delay cap and no jitter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::InvalidDuration`] if `base` cannot be parsed.
+    pub fn new<S: Into<SleepInput>>(base: S) -> Result<Self> {
+        Ok(RetryConfig {
+            base: base.into().to_duration()?,
+            factor: 2.0,
+            max_attempts: 3,
+            max_delay: Duration::from_secs(30),
+            jitter: 0.0,
+        })
+    }
+
+    /// Sets the growth factor applied to the delay after each failed attempt.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::NumberOutOfRange`] if `factor` is not a finite number of at
+    /// least 1.0.
+    pub fn with_factor(mut self, factor: f64) -> Result<Self> {
+        if !(factor.is_finite() && factor >= 1.0) {
+            return Err(SleepError::NumberOutOfRange(format!(
+                "Backoff factor must be a finite number >= 1.0, got {}",
+                factor
+            )));
+        }
+        self.factor = factor;
+        Ok(self)
+    }
+
+    /// Sets the total number of attempts, including the first one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::NumberOutOfRange`] if `max_attempts` is zero.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Result<Self> {
+        if max_attempts == 0 {
+            return Err(SleepError::NumberOutOfRange(
+                "Retry needs at least one attempt".to_string(),
+            ));
+        }
+        self.max_attempts = max_attempts;
+        Ok(self)
+    }
+
+    /// Caps every delay at `max_delay`.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the jitter fraction applied to each delay.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::NumberOutOfRange`] if `jitter` is outside `[0.0, 1.0]`.
+    pub fn with_jitter(mut self, jitter: f64) -> Result<Self> {
+        if !(0.0..=1.0).contains(&jitter) {
+            return Err(SleepError::NumberOutOfRange(format!(
+                "Jitter fraction must be within [0.0, 1.0], got {}",
+                jitter
+            )));
+        }
+        self.jitter = jitter;
+        Ok(self)
+    }
+
+    /// Returns the delay before the first retry.
+    pub fn base(&self) -> Duration {
+        self.base
+    }
+
+    /// Returns the growth factor applied after each failed attempt.
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    /// Returns the total number of attempts, including the first one.
+    pub fn max_attempts(&self) -> usize {
+        self.max_attempts
+    }
+
+    /// Returns the upper bound for any single delay.
+    pub fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    /// Returns the jitter fraction applied to each delay.
+    pub fn jitter(&self) -> f64 {
+        self.jitter
+    }
+
+    /// Iterate over the delays between attempts, before jitter is applied
+    pub(crate) fn backoff(&self) -> Backoff {
+        Backoff {
+            base: self.base,
+            factor: self.factor,
+            max: Some(self.max_delay),
+            attempt: 0,
+        }
+    }
+
+    /// Apply the configured jitter to a delay, keeping it under the cap
+    pub(crate) fn jittered(&self, delay: Duration) -> Duration {
+        crate::jitter::jittered(delay, self.jitter).min(self.max_delay)
+    }
+}
+
+/// Runs `op` until it succeeds, sleeping with capped, jittered exponential backoff
+/// between failed attempts.
+///
+/// Returns the first success of `op`, or its last error once
+/// [`RetryConfig::max_attempts`] is exhausted. The delays are slept with [`smart_sleep`],
+/// so the global time scale, the sleep limit, observers and statistics all apply. If a
+/// delay is rejected, for example by [`set_max_sleep`](crate::set_max_sleep), retrying
+/// stops early and the last error of `op` is returned. Only available with the `rand`
+/// feature.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{retry, RetryConfig};
+///
+/// let mut calls = 0;
+/// let result = retry(
+///     || {
+///         calls += 1;
+///         if calls < 3 { Err("not yet") } else { Ok(calls) }
+///     },
+///     RetryConfig::new("1ms").unwrap(),
+/// );
+/// assert_eq!(result, Ok(3));
+/// ```
+#[cfg(feature = "rand")]
+#[track_caller]
+pub fn retry<T, E, F>(mut op: F, config: RetryConfig) -> std::result::Result<T, E>
+where
+    F: FnMut() -> std::result::Result<T, E>,
+{
+    let mut backoff = config.backoff();
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= config.max_attempts => return Err(e),
+            Err(e) => {
+                if let Err(rejected) = smart_sleep(config.jittered(backoff.next_delay())) {
+                    log_rejected_delay(&rejected);
+                    return Err(e);
+                }
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Report a backoff delay that could not be slept, which ends the retries
+#[cfg(feature = "rand")]
+pub(crate) fn log_rejected_delay(error: &SleepError) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(%error, "backoff delay rejected, giving up on retries");
+    #[cfg(not(feature = "tracing"))]
+    let _ = error;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_schedule() -> Result<()> {
+        let mut backoff = Backoff::new("10ms", 2.0)?.with_max(Duration::from_millis(50));
+        let delays: Vec<Duration> = (0..5).map(|_| backoff.next_delay()).collect();
+        assert_eq!(delays, [10, 20, 40, 50, 50].map(Duration::from_millis));
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Duration::from_millis(10));
+
+        assert!(Backoff::new("10ms", 0.5).is_err());
+        assert!(Backoff::new("bogus", 2.0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_base_stays_zero() -> Result<()> {
+        // 2^1100 overflows an f64, which used to turn the zero delay into Duration::MAX
+        let mut backoff = Backoff::new(0, 2.0)?;
+        assert!((0..1100).all(|_| backoff.next_delay().is_zero()));
+
+        let mut calls = 0;
+        let result = retry_with_backoff(1100, 0, 2.0, None, || {
+            calls += 1;
+            Err::<(), _>(calls)
+        })?;
+        assert_eq!(result, Err(1100));
+        Ok(())
+    }
+
+    #[test]
+    fn test_retry_with_backoff_exhausts_attempts() -> Result<()> {
+        let mut calls = 0;
+        let start = std::time::Instant::now();
+        let result: std::result::Result<(), u32> =
+            retry_with_backoff(4, "10ms", 2.0, None, || {
+                calls += 1;
+                Err(calls)
+            })?;
+        let elapsed = start.elapsed();
+
+        assert_eq!(result, Err(4));
+        assert_eq!(calls, 4);
+        // Sleeps of 10ms, 20ms and 40ms between the four attempts
+        assert!(elapsed >= Duration::from_millis(70), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(300), "{:?}", elapsed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_retry_with_backoff_caps_and_validates() -> Result<()> {
+        let mut calls = 0;
+        let start = std::time::Instant::now();
+        let result = retry_with_backoff(4, "10ms", 10.0, Some(Duration::from_millis(15)), || {
+            calls += 1;
+            if calls < 4 {
+                Err("fail")
+            } else {
+                Ok("done")
+            }
+        })?;
+        let elapsed = start.elapsed();
+
+        assert_eq!(result, Ok("done"));
+        // 10ms, then two delays capped at 15ms instead of 100ms and 1s
+        assert!(elapsed >= Duration::from_millis(40), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(250), "{:?}", elapsed);
+
+        let never = || -> std::result::Result<(), ()> { panic!("op must not run") };
+        assert!(retry_with_backoff(0, "10ms", 2.0, None, never).is_err());
+        assert!(retry_with_backoff(3, "bogus", 2.0, None, never).is_err());
+        assert!(retry_with_backoff(3, "10ms", 0.5, None, never).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pow2_backoff() -> Result<()> {
+        let max = Duration::from_millis(100);
+        let delays: Result<Vec<Duration>> = (0..5).map(|n| pow2_backoff("10ms", n, max)).collect();
+        assert_eq!(delays?, [10, 20, 40, 80, 100].map(Duration::from_millis));
+
+        assert_eq!(pow2_backoff("10ms", 200, max)?, max);
+        assert_eq!(
+            pow2_backoff("1500us", 1, Duration::MAX)?,
+            Duration::from_millis(3)
+        );
+        assert_eq!(
+            pow2_backoff("10ms", 63, Duration::MAX)?,
+            Duration::from_secs(1 << 63) / 100
+        );
+        assert_eq!(pow2_backoff("10ms", 100, Duration::MAX)?, Duration::MAX);
+        assert_eq!(pow2_backoff(0, 200, max)?, Duration::ZERO);
+        assert!(pow2_backoff("bogus", 1, max).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_retry_follows_capped_jittered_schedule() -> Result<()> {
+        use std::time::Instant;
+
+        let config = RetryConfig::new("10ms")?
+            .with_max_attempts(5)?
+            .with_max_delay(Duration::from_millis(30))
+            .with_jitter(0.2)?;
+
+        let mut calls = Vec::new();
+        let result: std::result::Result<usize, String> = retry(
+            || {
+                calls.push(Instant::now());
+                if calls.len() < 4 {
+                    Err(format!("attempt {} failed", calls.len()))
+                } else {
+                    Ok(calls.len())
+                }
+            },
+            config,
+        );
+        assert_eq!(result, Ok(4));
+
+        // Nominal delays are 10ms, 20ms and 30ms (capped from 40ms), each jittered by 20%
+        let gaps: Vec<Duration> = calls.windows(2).map(|w| w[1] - w[0]).collect();
+        for (gap, nominal) in gaps.iter().zip([10u64, 20, 30]) {
+            assert!(
+                *gap >= Duration::from_millis(nominal * 8 / 10),
+                "{:?} < 80% of {}ms",
+                gap,
+                nominal
+            );
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_retry_returns_last_error() -> Result<()> {
+        let config = RetryConfig::new("1ms")?.with_max_attempts(3)?;
+
+        let mut calls = 0;
+        let result: std::result::Result<(), usize> = retry(
+            || {
+                calls += 1;
+                Err(calls)
+            },
+            config,
+        );
+        assert_eq!(result, Err(3));
+        Ok(())
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_retry_config_rejects_invalid_settings() -> Result<()> {
+        let config = RetryConfig::new("1ms")?;
+        assert!(config.clone().with_max_attempts(0).is_err());
+        assert!(config.clone().with_factor(0.5).is_err());
+        assert!(config.clone().with_factor(f64::NAN).is_err());
+        assert!(config.clone().with_jitter(1.5).is_err());
+        assert!(config.clone().with_jitter(-0.1).is_err());
+        assert!(RetryConfig::new("bogus").is_err());
+
+        let config = config.with_factor(3.0)?.with_jitter(0.5)?;
+        assert_eq!((config.factor(), config.jitter()), (3.0, 0.5));
+        assert_eq!(config.max_attempts(), 3);
+        Ok(())
+    }
+}
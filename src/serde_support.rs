@@ -0,0 +1,283 @@
+use crate::{format_duration, parse_sleep_duration, Result, SleepError, SleepInput};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+
+/// Parse a duration from a JSON value that may be a number or a string.
+///
+/// Numbers follow the numeric semantics of [`SleepInput::Number`]: they are milliseconds,
+/// and zero or negative values resolve to [`Duration::ZERO`]. Fractional numbers keep
+/// their sub-millisecond part. Strings are parsed with [`parse_sleep_duration`].
+///
+/// Only available with the `serde` feature.
+///
+/// # Errors
+///
+/// Returns [`SleepError::ParseError`] for JSON types other than numbers and strings, and
+/// [`SleepError::InvalidDuration`] for strings that cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use sleep_utils::parse_json_value;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_json_value(&json!(100)).unwrap(), Duration::from_millis(100));
+/// assert_eq!(parse_json_value(&json!("1m30s")).unwrap(), Duration::from_secs(90));
+/// assert!(parse_json_value(&json!(true)).is_err());
+/// ```
+pub fn parse_json_value(value: &Value) -> Result<Duration> {
+    match value {
+        Value::Number(number) => {
+            if let Some(millis) = number.as_u64() {
+                Ok(Duration::from_millis(millis))
+            } else if number.as_i64().is_some() {
+                // Negative integers
+                Ok(Duration::ZERO)
+            } else {
+                let millis = number.as_f64().unwrap_or(0.0);
+                if millis <= 0.0 {
+                    return Ok(Duration::ZERO);
+                }
+                Duration::try_from_secs_f64(millis / 1000.0)
+                    .map_err(|e| SleepError::NumberOutOfRange(format!("{}: {}", number, e)))
+            }
+        }
+        Value::String(text) => parse_sleep_duration(text),
+        other => Err(SleepError::ParseError(format!(
+            "Expected a number or string duration, got {}",
+            json_type_name(other)
+        ))),
+    }
+}
+
+/// Human-readable name of a JSON value's type
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Converts a JSON value into a [`SleepInput`] without resolving it.
+///
+/// Integers become [`SleepInput::Number`] and strings become [`SleepInput::Text`], so
+/// they keep their usual semantics when the input is resolved later. Numbers that don't
+/// fit an `isize`, or that have a fractional part, become a [`SleepInput::Duration`] of
+/// milliseconds. Only available with the `serde` feature.
+///
+/// # Errors
+///
+/// Returns [`SleepError::ParseError`] for JSON types other than numbers and strings.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use sleep_utils::SleepInput;
+/// use std::time::Duration;
+///
+/// let input = SleepInput::try_from(json!("1m30s")).unwrap();
+/// assert_eq!(input.to_duration().unwrap(), Duration::from_secs(90));
+/// assert!(SleepInput::try_from(json!([1, 2])).is_err());
+/// ```
+impl TryFrom<Value> for SleepInput {
+    type Error = SleepError;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Number(ref number) => match number.as_i64().map(isize::try_from) {
+                Some(Ok(n)) => Ok(SleepInput::Number(n)),
+                _ => parse_json_value(&value).map(SleepInput::Duration),
+            },
+            Value::String(text) => Ok(SleepInput::Text(text)),
+            other => Err(SleepError::ParseError(format!(
+                "Expected a number or string duration, got {}",
+                json_type_name(&other)
+            ))),
+        }
+    }
+}
+
+/// Serializes inputs as the canonical string of [`format_duration`], and durations as
+/// milliseconds.
+///
+/// Numbers, floats, text and [`SleepInput::Until`] deadlines are resolved first, so
+/// `"90s"` serializes as `"1m30s"` and a deadline as the time remaining. A
+/// [`SleepInput::Duration`] with a sub-millisecond part serializes as fractional
+/// milliseconds. Only available with the `serde` feature.
+///
+/// # Errors
+///
+/// Fails with the serializer's error when a text input cannot be parsed.
+impl Serialize for SleepInput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            SleepInput::Duration(duration) => serialize_millis(*duration, serializer),
+            _ => {
+                let duration = self.to_duration().map_err(ser::Error::custom)?;
+                serializer.serialize_str(&format_duration(duration))
+            }
+        }
+    }
+}
+
+/// Write a duration as whole milliseconds when exact, fractional milliseconds otherwise
+fn serialize_millis<S: Serializer>(
+    duration: Duration,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    match u64::try_from(duration.as_millis()) {
+        Ok(millis) if duration.subsec_nanos().is_multiple_of(1_000_000) => {
+            serializer.serialize_u64(millis)
+        }
+        _ => serializer.serialize_f64(duration.as_secs_f64() * 1000.0),
+    }
+}
+
+/// Deserializes integers into [`SleepInput::Number`], fractional numbers into
+/// [`SleepInput::Duration`] (as milliseconds) and strings into [`SleepInput::Text`].
+///
+/// Strings are validated with [`parse_sleep_duration`], so invalid durations fail while
+/// deserializing. Only available with the `serde` feature.
+impl<'de> Deserialize<'de> for SleepInput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(SleepInputVisitor)
+    }
+}
+
+struct SleepInputVisitor;
+
+impl Visitor<'_> for SleepInputVisitor {
+    type Value = SleepInput;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a number of milliseconds or a duration string")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> std::result::Result<SleepInput, E> {
+        match isize::try_from(value) {
+            Ok(n) => Ok(SleepInput::Number(n)),
+            Err(_) if value < 0 => Ok(SleepInput::Number(-1)),
+            Err(_) => self.visit_u64(value as u64),
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> std::result::Result<SleepInput, E> {
+        Ok(SleepInput::from(value))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> std::result::Result<SleepInput, E> {
+        if value.is_nan() || value <= 0.0 {
+            return Ok(SleepInput::Duration(Duration::ZERO));
+        }
+        Duration::try_from_secs_f64(value / 1000.0)
+            .map(SleepInput::Duration)
+            .map_err(|e| E::custom(format!("{}ms: {}", value, e)))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> std::result::Result<SleepInput, E> {
+        value.parse().map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::time::Instant;
+
+    #[test]
+    fn test_parse_json_value() -> Result<()> {
+        assert_eq!(parse_json_value(&json!(100))?, Duration::from_millis(100));
+        assert_eq!(parse_json_value(&json!(-100))?, Duration::ZERO);
+        assert_eq!(parse_json_value(&json!(1.5))?, Duration::from_micros(1500));
+        assert_eq!(parse_json_value(&json!("1m30s"))?, Duration::from_secs(90));
+
+        assert!(matches!(
+            parse_json_value(&json!(true)),
+            Err(SleepError::ParseError(_))
+        ));
+        assert!(matches!(
+            parse_json_value(&json!("bogus")),
+            Err(SleepError::InvalidDuration(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sleep_input_try_from_value() -> Result<()> {
+        let number = SleepInput::try_from(json!(250))?;
+        assert!(matches!(number, SleepInput::Number(250)));
+        let negative = SleepInput::try_from(json!(-5))?;
+        assert!(matches!(negative, SleepInput::Number(-5)));
+
+        let fractional = SleepInput::try_from(json!(1.5))?;
+        assert_eq!(fractional.to_duration()?, Duration::from_micros(1500));
+
+        let text = SleepInput::try_from(json!("2s"))?;
+        assert!(matches!(text, SleepInput::Text(ref t) if t == "2s"));
+        assert_eq!(text.to_duration()?, Duration::from_secs(2));
+
+        let error = SleepInput::try_from(json!([100, 200])).unwrap_err();
+        assert!(matches!(error, SleepError::ParseError(ref m) if m.contains("array")));
+        assert!(SleepInput::try_from(json!(null)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sleep_input_round_trip() -> Result<()> {
+        let to_json = |input: &SleepInput| serde_json::to_string(input).unwrap();
+        let from_json = |json: &str| serde_json::from_str::<SleepInput>(json);
+
+        let number = SleepInput::Number(500);
+        assert_eq!(to_json(&number), r#""500ms""#);
+        let back = from_json(&to_json(&number)).unwrap();
+        assert_eq!(back.to_duration()?, Duration::from_millis(500));
+        assert_eq!(to_json(&SleepInput::Number(-5)), r#""0ms""#);
+
+        let float = SleepInput::Float(1.5);
+        assert_eq!(to_json(&float), r#""1ms500us""#);
+        let back = from_json(&to_json(&float)).unwrap();
+        assert_eq!(back.to_duration()?, Duration::from_micros(1500));
+
+        let text = SleepInput::from("90 seconds");
+        assert_eq!(to_json(&text), r#""1m30s""#);
+        let back = from_json(&to_json(&text)).unwrap();
+        assert!(matches!(back, SleepInput::Text(ref t) if t == "1m30s"));
+        assert_eq!(back.to_duration()?, Duration::from_secs(90));
+        assert!(serde_json::to_string(&SleepInput::from("5 banana")).is_err());
+
+        let until = SleepInput::Until(Instant::now() - Duration::from_secs(1));
+        assert_eq!(to_json(&until), r#""0ms""#);
+
+        let duration = SleepInput::Duration(Duration::from_millis(1500));
+        assert_eq!(to_json(&duration), "1500");
+        let back = from_json(&to_json(&duration)).unwrap();
+        assert_eq!(back.to_duration()?, Duration::from_millis(1500));
+
+        let fine = SleepInput::Duration(Duration::from_micros(2500));
+        assert_eq!(to_json(&fine), "2.5");
+        let back = from_json(&to_json(&fine)).unwrap();
+        assert_eq!(back.to_duration()?, Duration::from_micros(2500));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sleep_input_deserialize_errors() {
+        let error = serde_json::from_str::<SleepInput>(r#""5 banana""#).unwrap_err();
+        assert!(error.to_string().contains("5 banana"), "{}", error);
+        assert!(serde_json::from_str::<SleepInput>("true").is_err());
+        assert!(serde_json::from_str::<SleepInput>("[1]").is_err());
+
+        let negative = serde_json::from_str::<SleepInput>("-20").unwrap();
+        assert!(matches!(negative, SleepInput::Number(-20)));
+    }
+}
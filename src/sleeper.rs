@@ -0,0 +1,194 @@
+use crate::{smart_sleep, PreciseSleeper, Result, SleepError, SleepInput};
+use std::time::Duration;
+
+/// A reusable sleep policy: clamping, jitter and precision applied to every sleep.
+///
+/// Build one with [`Sleeper::builder`] and share it instead of passing the same options
+/// to every call. Each [`sleep`](Sleeper::sleep) resolves its input, applies jitter,
+/// clamps the result into the configured range and then sleeps with
+/// [`smart_sleep`](crate::smart_sleep), or with a [`PreciseSleeper`] in precise mode.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::Sleeper;
+/// use std::time::Duration;
+///
+/// let sleeper = Sleeper::builder().min("1ms").max("5ms").build().unwrap();
+/// assert_eq!(sleeper.sleep("1h").unwrap(), Duration::from_millis(5));
+/// assert_eq!(sleeper.sleep("100us").unwrap(), Duration::from_millis(1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sleeper {
+    min: Duration,
+    max: Duration,
+    #[cfg(feature = "rand")]
+    jitter: f64,
+    precise: bool,
+}
+
+impl Sleeper {
+    /// Starts building a sleeper with no bounds, no jitter and regular precision.
+    pub fn builder() -> SleeperBuilder {
+        SleeperBuilder::default()
+    }
+
+    /// Sleeps for the input with this policy applied and returns the duration used.
+    ///
+    /// Zero and negative inputs skip sleeping and are not raised to the minimum. The
+    /// returned duration is before the global time scale, which still applies to the
+    /// sleep itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::InvalidDuration`] if the input string cannot be parsed, and
+    /// propagates errors from the underlying sleep.
+    #[track_caller]
+    pub fn sleep<S: Into<SleepInput>>(&self, input: S) -> Result<Duration> {
+        let requested = input.into().to_duration()?;
+        if requested.is_zero() {
+            return Ok(Duration::ZERO);
+        }
+
+        #[cfg(feature = "rand")]
+        let requested = crate::jitter::jittered(requested, self.jitter);
+
+        let duration = requested.clamp(self.min, self.max);
+        if self.precise {
+            PreciseSleeper::new().sleep(duration)?;
+        } else {
+            smart_sleep(duration)?;
+        }
+        Ok(duration)
+    }
+}
+
+/// Builder for [`Sleeper`], created with [`Sleeper::builder`].
+///
+/// Bounds accept anything that converts into a [`SleepInput`] and are resolved once by
+/// [`build`](SleeperBuilder::build).
+#[derive(Debug, Clone, Default)]
+pub struct SleeperBuilder {
+    min: Option<SleepInput>,
+    max: Option<SleepInput>,
+    #[cfg(feature = "rand")]
+    jitter: f64,
+    precise: bool,
+}
+
+impl SleeperBuilder {
+    /// Raises every non-zero sleep to at least `min`.
+    pub fn min(mut self, min: impl Into<SleepInput>) -> Self {
+        self.min = Some(min.into());
+        self
+    }
+
+    /// Caps every sleep at `max`.
+    pub fn max(mut self, max: impl Into<SleepInput>) -> Self {
+        self.max = Some(max.into());
+        self
+    }
+
+    /// Randomizes each sleep within `duration * (1 ± fraction)` before clamping.
+    ///
+    /// Only available with the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn jitter(mut self, fraction: f64) -> Self {
+        self.jitter = fraction;
+        self
+    }
+
+    /// Spins through the end of each sleep for accuracy, see [`PreciseSleeper`].
+    pub fn precise(mut self, precise: bool) -> Self {
+        self.precise = precise;
+        self
+    }
+
+    /// Resolves the configuration into a [`Sleeper`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::InvalidDuration`] if a bound cannot be parsed or the
+    /// minimum exceeds the maximum, or [`SleepError::NumberOutOfRange`] if the jitter
+    /// fraction is outside `[0.0, 1.0]`.
+    pub fn build(self) -> Result<Sleeper> {
+        let resolve = |bound: Option<SleepInput>, default| match bound {
+            Some(bound) => bound.to_duration(),
+            None => Ok(default),
+        };
+        let min = resolve(self.min, Duration::ZERO)?;
+        let max = resolve(self.max, Duration::MAX)?;
+        if min > max {
+            return Err(SleepError::InvalidDuration(format!(
+                "Sleeper minimum {:?} is greater than maximum {:?}",
+                min, max
+            )));
+        }
+
+        #[cfg(feature = "rand")]
+        if !(0.0..=1.0).contains(&self.jitter) {
+            return Err(SleepError::NumberOutOfRange(format!(
+                "Jitter fraction must be within [0.0, 1.0], got {}",
+                self.jitter
+            )));
+        }
+
+        Ok(Sleeper {
+            min,
+            max,
+            #[cfg(feature = "rand")]
+            jitter: self.jitter,
+            precise: self.precise,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_sleeper_clamps_to_max() -> Result<()> {
+        let sleeper = Sleeper::builder().max("20ms").build()?;
+        let start = Instant::now();
+        assert_eq!(sleeper.sleep("10s")?, Duration::from_millis(20));
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(20));
+        assert!(elapsed < Duration::from_secs(1), "{:?}", elapsed);
+
+        assert_eq!(sleeper.sleep("5ms")?, Duration::from_millis(5));
+        assert_eq!(sleeper.sleep(0)?, Duration::ZERO);
+        assert!(sleeper.sleep("bogus").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sleeper_min_and_precise() -> Result<()> {
+        let sleeper = Sleeper::builder().min("2ms").precise(true).build()?;
+        let start = Instant::now();
+        assert_eq!(sleeper.sleep("10us")?, Duration::from_millis(2));
+        assert!(start.elapsed() >= Duration::from_millis(2));
+        assert_eq!(sleeper.sleep(-5)?, Duration::ZERO);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sleeper_builder_validation() {
+        assert!(Sleeper::builder().min("2s").max("1s").build().is_err());
+        assert!(Sleeper::builder().max("bogus").build().is_err());
+        assert!(Sleeper::builder().min("1s").max("1s").build().is_ok());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sleeper_jitter_stays_clamped() -> Result<()> {
+        let sleeper = Sleeper::builder().max("4ms").jitter(0.5).build()?;
+        for _ in 0..20 {
+            let slept = sleeper.sleep("4ms")?;
+            assert!(slept >= Duration::from_millis(2) && slept <= Duration::from_millis(4));
+        }
+        assert!(Sleeper::builder().jitter(1.5).build().is_err());
+        Ok(())
+    }
+}
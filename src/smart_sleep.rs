@@ -1,5 +1,7 @@
-use crate::{parse_sleep_duration, Result};
-use std::time::Duration;
+use crate::config::{apply_granularity, apply_time_scale, check_max_sleep, zero_sleep_yields};
+use crate::{parse_sleep_duration, Result, SleepError, Unit};
+use std::panic::Location;
+use std::time::{Duration, Instant};
 
 /// Smart sleep function that supports multiple input formats.
 ///
@@ -13,7 +15,8 @@ use std::time::Duration;
 /// - **Multiple units**: `"1m30s"`, `"1h2m3s"`, `"2s500ms"`
 /// - **Plain text**: `"100"` (interpreted as milliseconds)
 /// - **Duration objects**: `Duration::from_millis(100)`
-/// - **Zero/negative**: `0`, `-100` (no sleep performed)
+/// - **Zero/negative**: `0`, `-100` (no sleep performed, or a thread yield if
+///   [`set_zero_sleep_yields`](crate::set_zero_sleep_yields) is enabled)
 ///
 /// # Examples
 ///
@@ -45,23 +48,139 @@ use std::time::Duration;
 /// # Errors
 ///
 /// Returns [`SleepError::InvalidDuration`] if the input string cannot be parsed
-/// as a valid duration.
+/// as a valid duration, or [`SleepError::NumberOutOfRange`] if the resolved duration
+/// exceeds the limit set with [`set_max_sleep`](crate::set_max_sleep).
 ///
 /// # Panics
 ///
 /// This function does not panic. All errors are returned as [`Result`].
+///
+/// # Statistics
+///
+/// With the `stats` feature, every call is recorded in [`sleep_stats`](crate::sleep_stats)
+/// together with its caller location, so the code paths that sleep the most can be found.
+#[track_caller]
 pub fn smart_sleep<S>(input: S) -> Result<()>
+where
+    S: Into<SleepInput>,
+{
+    smart_sleep_report(input).map(|_| ())
+}
+
+/// The outcome of a [`smart_sleep_report`] call
+#[derive(Debug, Clone)]
+pub struct SleepReport {
+    /// The input as it was passed in
+    pub requested: SleepInput,
+    /// The duration the input resolved to, after time scale and granularity
+    pub resolved: Duration,
+    /// Whether the thread actually slept
+    pub slept: bool,
+}
+
+/// Sleeps like [`smart_sleep`] and reports what happened.
+///
+/// The returned [`SleepReport`] carries the original input, the resolved duration and
+/// whether a sleep took place, which makes this the single observable entry point for
+/// callers that need more than success or failure.
+///
+/// # Errors
+///
+/// Returns [`SleepError::InvalidDuration`] if the input string cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::smart_sleep_report;
+/// use std::time::Duration;
+///
+/// let report = smart_sleep_report("1ms").unwrap();
+/// assert!(report.slept);
+/// assert_eq!(report.resolved, Duration::from_millis(1));
+///
+/// assert!(!smart_sleep_report(0).unwrap().slept);
+/// ```
+#[track_caller]
+pub fn smart_sleep_report<S>(input: S) -> Result<SleepReport>
 where
     S: Into<SleepInput>,
 {
     let sleep_input = input.into();
+    let duration = run_sleep(&sleep_input, Location::caller(), std::thread::sleep)?;
+
+    Ok(SleepReport {
+        requested: sleep_input,
+        resolved: duration,
+        slept: !duration.is_zero(),
+    })
+}
+
+/// Resolve an input and report it to the stats, observers and tracing, once per sleep
+///
+/// Every sleeping entry point goes through this, so each sleep is counted and traced
+/// exactly once. Returns the resolved duration, which is zero for skipped sleeps.
+pub(crate) fn begin_sleep(
+    input: &SleepInput,
+    caller: &'static Location<'static>,
+) -> Result<Duration> {
+    let duration = resolve_sleep(input)?;
+
+    #[cfg(feature = "stats")]
+    crate::stats::record(caller, duration);
+    #[cfg(not(feature = "stats"))]
+    let _ = caller;
+
+    crate::observers::notify(input, duration);
+
+    #[cfg(feature = "tracing")]
+    if duration.is_zero() {
+        tracing::trace!(
+            input = ?input,
+            resolved = ?duration,
+            skipped = true,
+            "skipping sleep for zero or negative input"
+        );
+    } else {
+        tracing::debug!(input = ?input, resolved = ?duration, skipped = false, "smart_sleep");
+    }
+
+    Ok(duration)
+}
+
+/// Run a blocking sleep through [`begin_sleep`], handing non-zero durations to `sleep`
+///
+/// Skipped sleeps yield the thread instead when
+/// [`set_zero_sleep_yields`](crate::set_zero_sleep_yields) is enabled.
+pub(crate) fn run_sleep(
+    input: &SleepInput,
+    caller: &'static Location<'static>,
+    sleep: impl FnOnce(Duration),
+) -> Result<Duration> {
+    let duration = begin_sleep(input, caller)?;
+    if !duration.is_zero() {
+        sleep(duration);
+    } else if zero_sleep_yields() {
+        std::thread::yield_now();
+    }
+    Ok(duration)
+}
 
-    if sleep_input.should_sleep() {
-        let duration = sleep_input.to_duration()?;
-        std::thread::sleep(duration);
+/// Resolve how long `smart_sleep` would sleep for an input
+///
+/// Applies the global time scale and granularity, then the sleep limit. Returns
+/// [`Duration::ZERO`] for inputs that should not sleep at all.
+pub(crate) fn resolve_sleep(input: &SleepInput) -> Result<Duration> {
+    // Text is parsed once here instead of once more in should_sleep
+    let duration = match input {
+        SleepInput::Text(_) => input.to_duration()?,
+        _ if input.should_sleep() => input.to_duration()?,
+        _ => Duration::ZERO,
+    };
+    if duration.is_zero() {
+        return Ok(Duration::ZERO);
     }
 
-    Ok(())
+    check_max_sleep(apply_granularity(apply_time_scale(duration)))
 }
 
 /// Represents different types of sleep inputs.
@@ -72,16 +191,25 @@ where
 /// # Variants
 ///
 /// - `Number(isize)`: Numeric input interpreted as milliseconds
+/// - `Float(f64)`: Fractional numeric input, also interpreted as milliseconds
 /// - `Text(String)`: String input that will be parsed for duration
 /// - `Duration(Duration)`: Standard duration object
+/// - `Until(Instant)`: Absolute deadline; sleeps for the time remaining until it
+///
+/// Every variant resolves with nanosecond precision: `Duration::from_nanos(500)` and
+/// `"500ns"` resolve to the same duration, and neither is rounded to milliseconds.
 #[derive(Debug, Clone)]
 pub enum SleepInput {
     /// Numeric input interpreted as milliseconds
     Number(isize),
+    /// Fractional numeric input interpreted as milliseconds
+    Float(f64),
     /// Text input that will be parsed for duration information
     Text(String),
     /// Standard duration object
     Duration(Duration),
+    /// Absolute deadline, resolved to the time remaining when it is used
+    Until(Instant),
 }
 
 // Implement various From traits for seamless conversion
@@ -97,12 +225,129 @@ impl From<isize> for SleepInput {
     }
 }
 
+/// Unsigned milliseconds that fit an `isize` become a [`SleepInput::Number`]; larger
+/// values are kept exactly as a [`SleepInput::Duration`] instead of wrapping.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::SleepInput;
+/// use std::time::Duration;
+///
+/// let input = SleepInput::from(u64::MAX);
+/// assert_eq!(input.to_duration().unwrap(), Duration::from_millis(u64::MAX));
+/// ```
+impl From<u64> for SleepInput {
+    fn from(value: u64) -> Self {
+        match isize::try_from(value) {
+            Ok(n) => SleepInput::Number(n),
+            Err(_) => SleepInput::Duration(Duration::from_millis(value)),
+        }
+    }
+}
+
+/// Converts like `From<u64>`, so values never wrap on 32-bit targets.
+impl From<u32> for SleepInput {
+    fn from(value: u32) -> Self {
+        SleepInput::from(u64::from(value))
+    }
+}
+
+/// Compares inputs by the duration they resolve to, not by variant.
+///
+/// `SleepInput::from("1s")`, `SleepInput::from(1000)` and
+/// `SleepInput::from(Duration::from_secs(1))` are all equal, and every zero or negative
+/// input equals every other. Two future [`SleepInput::Until`] deadlines compare their
+/// instants. A deadline in the past resolves to zero like any other elapsed input, so it
+/// equals every other past deadline and every zero input. A deadline and a relative input
+/// compare the time remaining right now, so that result can change over time.
+///
+/// Inputs that fail to resolve, such as unparseable text or a NaN float, are only equal
+/// to the same variant holding the same raw value (floats compared bit for bit). This
+/// keeps equality reflexive, but because resolution depends on global settings and the
+/// clock, `SleepInput` does not implement `Eq` or `Hash`.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::SleepInput;
+/// use std::time::Duration;
+///
+/// assert_eq!(SleepInput::from("1s"), SleepInput::from(1000));
+/// assert_eq!(SleepInput::from("1m30s"), SleepInput::from(Duration::from_secs(90)));
+/// assert_ne!(SleepInput::from("bogus"), SleepInput::from("junk"));
+/// ```
+impl PartialEq for SleepInput {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SleepInput::Until(a), SleepInput::Until(b)) if *a.min(b) > Instant::now() => a == b,
+            _ => match (self.to_duration(), other.to_duration()) {
+                (Ok(a), Ok(b)) => a == b,
+                (Err(_), Err(_)) => match (self, other) {
+                    (SleepInput::Text(a), SleepInput::Text(b)) => a == b,
+                    (SleepInput::Float(a), SleepInput::Float(b)) => a.to_bits() == b.to_bits(),
+                    _ => false,
+                },
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Floats are milliseconds, like integers, so `1.5` is 1.5ms rather than 1.5s.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::SleepInput;
+/// use std::time::Duration;
+///
+/// let input = SleepInput::from(1.5);
+/// assert_eq!(input.to_duration().unwrap(), Duration::from_micros(1500));
+/// assert!(!SleepInput::from(f64::NAN).should_sleep());
+/// ```
+impl From<f64> for SleepInput {
+    fn from(value: f64) -> Self {
+        SleepInput::Float(value)
+    }
+}
+
+impl From<f32> for SleepInput {
+    fn from(value: f32) -> Self {
+        SleepInput::Float(f64::from(value))
+    }
+}
+
 impl From<&str> for SleepInput {
     fn from(value: &str) -> Self {
         SleepInput::Text(value.to_string())
     }
 }
 
+/// Parses text eagerly, so invalid durations are reported when the input is built
+/// instead of when it is slept on. The result is a [`SleepInput::Text`], exactly like
+/// the infallible `From<&str>` conversion.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::SleepInput;
+/// use std::time::Duration;
+///
+/// let input: SleepInput = "1m30s".parse().unwrap();
+/// assert_eq!(input.to_duration().unwrap(), Duration::from_secs(90));
+///
+/// assert!("1 banana".parse::<SleepInput>().is_err());
+/// ```
+impl std::str::FromStr for SleepInput {
+    type Err = SleepError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_sleep_duration(s)?;
+        Ok(SleepInput::Text(s.to_string()))
+    }
+}
+
 impl From<String> for SleepInput {
     fn from(value: String) -> Self {
         SleepInput::Text(value)
@@ -115,11 +360,70 @@ impl From<Duration> for SleepInput {
     }
 }
 
+impl From<Instant> for SleepInput {
+    fn from(value: Instant) -> Self {
+        SleepInput::Until(value)
+    }
+}
+
+impl From<(u64, Unit)> for SleepInput {
+    fn from((value, unit): (u64, Unit)) -> Self {
+        SleepInput::Duration(unit.duration(value))
+    }
+}
+
 impl SleepInput {
+    /// Builds a [`SleepInput::Duration`] of `millis` milliseconds in a `const` context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sleep_utils::{smart_sleep, SleepInput};
+    ///
+    /// const DELAY: SleepInput = SleepInput::from_millis_const(1);
+    /// smart_sleep(DELAY).unwrap();
+    /// ```
+    pub const fn from_millis_const(millis: u64) -> SleepInput {
+        SleepInput::Duration(Duration::from_millis(millis))
+    }
+
+    /// Builds a [`SleepInput::Duration`] from whole seconds and extra nanoseconds.
+    ///
+    /// Unlike [`Duration::new`], this never panics: nanoseconds beyond one second carry
+    /// into the seconds, and a total that does not fit in a [`Duration`] is an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::NumberOutOfRange`] if the total overflows [`Duration`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sleep_utils::SleepInput;
+    /// use std::time::Duration;
+    ///
+    /// let input = SleepInput::from_parts(1, 1_500_000_000).unwrap();
+    /// assert_eq!(input.to_duration().unwrap(), Duration::from_millis(2500));
+    ///
+    /// assert!(SleepInput::from_parts(u64::MAX, 1_000_000_000).is_err());
+    /// ```
+    pub fn from_parts(secs: u64, nanos: u32) -> Result<SleepInput> {
+        let carry = u64::from(nanos / 1_000_000_000);
+        let secs = secs.checked_add(carry).ok_or_else(|| {
+            SleepError::NumberOutOfRange(format!("Duration of {}s + {}ns overflows", secs, nanos))
+        })?;
+        Ok(SleepInput::Duration(Duration::new(
+            secs,
+            nanos % 1_000_000_000,
+        )))
+    }
+
     /// Determines whether sleep should be performed for this input.
     ///
-    /// Returns `false` for zero or negative numeric values, allowing
-    /// the caller to skip sleep operations when appropriate.
+    /// Returns `false` for zero or negative numeric values and for NaN, allowing
+    /// the caller to skip sleep operations when appropriate. Text is parsed, so `"0s"`
+    /// and `"0h0m0s"` return `false` too; text that fails to parse returns `true` so that
+    /// [`to_duration`](Self::to_duration) can report the error.
     ///
     /// # Examples
     ///
@@ -138,14 +442,10 @@ impl SleepInput {
     pub fn should_sleep(&self) -> bool {
         match self {
             SleepInput::Number(n) => *n > 0,
-            SleepInput::Text(text) => {
-                if let Ok(n) = text.parse::<isize>() {
-                    n > 0
-                } else {
-                    true
-                }
-            }
+            SleepInput::Float(value) => *value > 0.0,
+            SleepInput::Text(text) => parse_sleep_duration(text).map_or(true, |d| !d.is_zero()),
             SleepInput::Duration(duration) => !duration.is_zero(),
+            SleepInput::Until(deadline) => *deadline > Instant::now(),
         }
     }
 
@@ -156,7 +456,8 @@ impl SleepInput {
     ///
     /// # Errors
     ///
-    /// Returns [`SleepError::InvalidDuration`] if text input cannot be parsed.
+    /// Returns [`SleepError::InvalidDuration`] if text input cannot be parsed, or
+    /// [`SleepError::NumberOutOfRange`] for a NaN or infinite [`SleepInput::Float`].
     ///
     /// # Examples
     ///
@@ -177,8 +478,486 @@ impl SleepInput {
                     Ok(Duration::from_millis(*n as u64))
                 }
             }
+            SleepInput::Float(value) => {
+                if !value.is_finite() {
+                    Err(SleepError::NumberOutOfRange(format!(
+                        "Millisecond value must be finite, got {}",
+                        value
+                    )))
+                } else if *value <= 0.0 {
+                    Ok(Duration::ZERO)
+                } else {
+                    Duration::try_from_secs_f64(value / 1000.0)
+                        .map_err(|e| SleepError::NumberOutOfRange(format!("{}ms: {}", value, e)))
+                }
+            }
             SleepInput::Text(text) => parse_sleep_duration(text),
             SleepInput::Duration(duration) => Ok(*duration),
+            SleepInput::Until(deadline) => Ok(deadline.saturating_duration_since(Instant::now())),
+        }
+    }
+
+    /// Returns the time left until an [`SleepInput::Until`] deadline, without sleeping.
+    ///
+    /// Deadlines in the past yield `Some(Duration::ZERO)`. Relative inputs have no
+    /// absolute reference point and return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sleep_utils::SleepInput;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let input = SleepInput::from(Instant::now() + Duration::from_secs(60));
+    /// assert!(input.remaining().unwrap() > Duration::from_secs(59));
+    ///
+    /// assert_eq!(SleepInput::from("1s").remaining(), None);
+    /// ```
+    pub fn remaining(&self) -> Option<Duration> {
+        match self {
+            SleepInput::Until(deadline) => Some(deadline.saturating_duration_since(Instant::now())),
+            _ => None,
+        }
+    }
+
+    /// Resolves the input and returns its whole-second count as a `u32`.
+    ///
+    /// Sub-second remainders are truncated and durations longer than
+    /// `u32::MAX` seconds saturate to `u32::MAX`, which suits APIs that
+    /// take a 32-bit seconds argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::InvalidDuration`] if text input cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sleep_utils::SleepInput;
+    ///
+    /// assert_eq!(SleepInput::from("1m30s").as_secs_u32_saturating().unwrap(), 90);
+    /// assert_eq!(SleepInput::from("999ms").as_secs_u32_saturating().unwrap(), 0);
+    /// ```
+    pub fn as_secs_u32_saturating(&self) -> Result<u32> {
+        let secs = self.to_duration()?.as_secs();
+        Ok(u32::try_from(secs).unwrap_or(u32::MAX))
+    }
+
+    /// Resolves the input and returns it as whole seconds plus sub-second nanoseconds.
+    ///
+    /// The pair matches the layout of `timespec`-like structures taken by FFI sleep
+    /// and timeout APIs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::InvalidDuration`] if text input cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sleep_utils::SleepInput;
+    ///
+    /// assert_eq!(SleepInput::from("1500ms").as_timespec().unwrap(), (1, 500_000_000));
+    /// ```
+    pub fn as_timespec(&self) -> Result<(u64, u32)> {
+        let duration = self.to_duration()?;
+        Ok((duration.as_secs(), duration.subsec_nanos()))
+    }
+
+    /// Resolves both inputs and subtracts `other` from `self`, flooring at zero.
+    ///
+    /// The result is a [`SleepInput::Duration`], which is handy for computing the
+    /// time left after some part of a budget has been spent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SleepError::InvalidDuration`] if either input cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sleep_utils::SleepInput;
+    /// use std::time::Duration;
+    ///
+    /// let left = SleepInput::from("1s").saturating_sub(&SleepInput::from(250)).unwrap();
+    /// assert_eq!(left.to_duration().unwrap(), Duration::from_millis(750));
+    /// ```
+    pub fn saturating_sub(&self, other: &SleepInput) -> Result<SleepInput> {
+        let remaining = self.to_duration()?.saturating_sub(other.to_duration()?);
+        Ok(SleepInput::Duration(remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_secs_u32_saturating() -> Result<()> {
+        assert_eq!(SleepInput::from("2m").as_secs_u32_saturating()?, 120);
+        assert_eq!(SleepInput::from(1500).as_secs_u32_saturating()?, 1);
+
+        // Sub-second values round down to zero seconds
+        assert_eq!(SleepInput::from("500ms").as_secs_u32_saturating()?, 0);
+
+        // Values beyond u32::MAX seconds saturate
+        let huge = Duration::from_secs(u32::MAX as u64 + 10);
+        assert_eq!(SleepInput::from(huge).as_secs_u32_saturating()?, u32::MAX);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_as_timespec() -> Result<()> {
+        assert_eq!(SleepInput::from("1500ms").as_timespec()?, (1, 500_000_000));
+        assert_eq!(SleepInput::from(-5).as_timespec()?, (0, 0));
+        assert!(SleepInput::from("bogus").as_timespec().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<()> {
+        let input: SleepInput = "1m30s".parse()?;
+        assert!(matches!(input, SleepInput::Text(ref text) if text == "1m30s"));
+        assert_eq!(input.to_duration()?, Duration::from_secs(90));
+        assert_eq!("-5".parse::<SleepInput>()?.to_duration()?, Duration::ZERO);
+
+        for invalid in ["abc", "5 banana", "1:99"] {
+            assert!(
+                matches!(
+                    invalid.parse::<SleepInput>(),
+                    Err(SleepError::InvalidDuration(_))
+                ),
+                "{}",
+                invalid
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_smart_sleep_report() -> Result<()> {
+        let report = smart_sleep_report(0)?;
+        assert!(!report.slept);
+        assert_eq!(report.resolved, Duration::ZERO);
+        assert!(matches!(report.requested, SleepInput::Number(0)));
+
+        let start = Instant::now();
+        let report = smart_sleep_report("10ms")?;
+        assert!(start.elapsed() >= Duration::from_millis(10));
+        assert!(report.slept);
+        assert_eq!(report.resolved, Duration::from_millis(10));
+        assert!(matches!(report.requested, SleepInput::Text(ref text) if text == "10ms"));
+
+        assert!(smart_sleep_report("bogus").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sub_millisecond_precision_is_uniform() -> Result<()> {
+        let from_duration = smart_sleep_report(Duration::from_nanos(500))?;
+        let from_text = smart_sleep_report("500ns")?;
+        assert!(from_duration.slept && from_text.slept);
+        assert_eq!(from_duration.resolved, Duration::from_nanos(500));
+        assert_eq!(from_duration.resolved, from_text.resolved);
+
+        for (text, nanos) in [
+            ("1.5us", 1_500),
+            ("0.0005ms", 500),
+            ("1.000000001s", 1_000_000_001),
+        ] {
+            assert_eq!(
+                SleepInput::from(text).to_duration()?,
+                SleepInput::from(Duration::from_nanos(nanos)).to_duration()?
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_unsigned() -> Result<()> {
+        for millis in [1, 1500, u64::from(u32::MAX), u64::MAX / 2, u64::MAX] {
+            assert_eq!(
+                SleepInput::from(millis).to_duration()?,
+                Duration::from_millis(millis)
+            );
+        }
+        assert!(matches!(SleepInput::from(250u64), SleepInput::Number(250)));
+        assert_eq!(
+            SleepInput::from(u32::MAX).to_duration()?,
+            Duration::from_millis(u64::from(u32::MAX))
+        );
+        assert!(!SleepInput::from(0u32).should_sleep());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_float() -> Result<()> {
+        assert_eq!(
+            SleepInput::from(2.5).to_duration()?,
+            Duration::from_micros(2500)
+        );
+        assert_eq!(
+            SleepInput::from(0.5f32).to_duration()?,
+            Duration::from_micros(500)
+        );
+
+        for value in [0.0, -0.0, -1.5, f64::NEG_INFINITY, f64::NAN] {
+            assert!(!SleepInput::from(value).should_sleep(), "{}", value);
+        }
+        assert_eq!(SleepInput::from(-1.5).to_duration()?, Duration::ZERO);
+        assert_eq!(SleepInput::from(0.0).to_duration()?, Duration::ZERO);
+
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(matches!(
+                SleepInput::from(value).to_duration(),
+                Err(SleepError::NumberOutOfRange(_))
+            ));
+        }
+        assert!(SleepInput::from(f64::INFINITY).should_sleep());
+        assert!(smart_sleep(f64::INFINITY).is_err());
+        assert!(smart_sleep(f64::NAN).is_ok());
+
+        let start = Instant::now();
+        smart_sleep(5.5)?;
+        assert!(start.elapsed() >= Duration::from_micros(5500));
+        Ok(())
+    }
+
+    #[test]
+    fn test_should_sleep_resolves_text() -> Result<()> {
+        for text in ["0s", "0ms", "0h0m0s", "0", "-5", "  0 seconds ", ""] {
+            let input = SleepInput::from(text);
+            assert!(!input.should_sleep(), "{:?}", text);
+            let report = smart_sleep_report(input)?;
+            assert!(!report.slept, "{:?}", text);
+        }
+        assert!(SleepInput::from("1ms").should_sleep());
+        // Invalid text still reaches to_duration so the error is reported
+        assert!(SleepInput::from("bogus").should_sleep());
+        assert!(smart_sleep("bogus").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_partial_eq_by_resolved_duration() {
+        let one_second = [
+            SleepInput::from("1s"),
+            SleepInput::from(1000),
+            SleepInput::from(1000.0),
+            SleepInput::from(Duration::from_secs(1)),
+            SleepInput::from("1000ms"),
+        ];
+        for a in &one_second {
+            for b in &one_second {
+                assert_eq!(a, b);
+            }
+        }
+        assert_ne!(SleepInput::from("1s"), SleepInput::from("2s"));
+        assert_eq!(SleepInput::from(0), SleepInput::from("-5"));
+
+        // Malformed text only equals identical malformed text
+        let bogus = SleepInput::from("bogus");
+        assert_eq!(bogus, bogus.clone());
+        assert_ne!(bogus, SleepInput::from("junk"));
+        assert_ne!(bogus, SleepInput::from(0));
+        let nan = SleepInput::from(f64::NAN);
+        assert_eq!(nan, nan.clone());
+        assert_ne!(nan, bogus);
+
+        let deadline = Instant::now() + Duration::from_secs(60);
+        assert_eq!(SleepInput::from(deadline), SleepInput::from(deadline));
+        assert_ne!(SleepInput::from(deadline), SleepInput::from("1s"));
+
+        // Past deadlines all resolve to zero, keeping equality transitive
+        let now = Instant::now();
+        let earlier = SleepInput::from(now - Duration::from_secs(2));
+        let later = SleepInput::from(now - Duration::from_secs(1));
+        assert_eq!(earlier, SleepInput::from(0));
+        assert_eq!(later, SleepInput::from(0));
+        assert_eq!(earlier, later);
+        assert_ne!(earlier, SleepInput::from(deadline));
+    }
+
+    #[test]
+    fn test_from_millis_const() -> Result<()> {
+        const DELAY: SleepInput = SleepInput::from_millis_const(20);
+        assert_eq!(DELAY.to_duration()?, Duration::from_millis(20));
+
+        let start = Instant::now();
+        smart_sleep(DELAY)?;
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        Ok(())
+    }
+
+    #[test]
+    fn test_saturating_sub() -> Result<()> {
+        let a = SleepInput::from("1s");
+        assert_eq!(
+            a.saturating_sub(&SleepInput::from("1500ms"))?
+                .to_duration()?,
+            Duration::ZERO
+        );
+        assert_eq!(
+            a.saturating_sub(&SleepInput::from("400ms"))?
+                .to_duration()?,
+            Duration::from_millis(600)
+        );
+        assert!(a.saturating_sub(&SleepInput::from("bogus")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_until_remaining() -> Result<()> {
+        let future = SleepInput::from(Instant::now() + Duration::from_millis(200));
+        let remaining = future.remaining().unwrap();
+        assert!(remaining > Duration::from_millis(100) && remaining <= Duration::from_millis(200));
+        assert!(future.should_sleep());
+
+        let past = SleepInput::from(Instant::now() - Duration::from_millis(10));
+        assert_eq!(past.remaining(), Some(Duration::ZERO));
+        assert!(!past.should_sleep());
+        assert_eq!(past.to_duration()?, Duration::ZERO);
+
+        assert_eq!(SleepInput::from(100).remaining(), None);
+        assert_eq!(SleepInput::from(Duration::from_secs(1)).remaining(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sleep_until_variant() -> Result<()> {
+        let deadline = Instant::now() + Duration::from_millis(20);
+        smart_sleep(deadline)?;
+        assert!(Instant::now() >= deadline);
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflowing_components_are_safe() -> Result<()> {
+        assert_eq!(
+            SleepInput::from_parts(u64::MAX, 999_999_999)?.to_duration()?,
+            Duration::MAX
+        );
+        assert!(matches!(
+            SleepInput::from_parts(u64::MAX, u32::MAX),
+            Err(SleepError::NumberOutOfRange(_))
+        ));
+
+        // Unit-based construction saturates instead of panicking
+        assert_eq!(
+            Unit::Hours.duration(u64::MAX),
+            Duration::from_secs(u64::MAX)
+        );
+        assert_eq!(
+            SleepInput::from((u64::MAX, Unit::Minutes)).to_duration()?,
+            Duration::from_secs(u64::MAX)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_value_unit_pair() -> Result<()> {
+        assert_eq!(
+            SleepInput::from((250u64, Unit::Millis)).to_duration()?,
+            Duration::from_millis(250)
+        );
+        assert_eq!(
+            SleepInput::from((3u64, Unit::Minutes)).to_duration()?,
+            Duration::from_secs(180)
+        );
+        assert_eq!(
+            SleepInput::from((2u64, Unit::Hours)).to_duration()?,
+            Duration::from_secs(7200)
+        );
+        assert!(!SleepInput::from((0u64, Unit::Seconds)).should_sleep());
+
+        let start = std::time::Instant::now();
+        smart_sleep((2u64, Unit::Seconds))?;
+        assert!(start.elapsed() >= Duration::from_secs(2));
+        Ok(())
+    }
+
+    #[cfg(feature = "tracing")]
+    fn capture_events<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+        use tracing_subscriber::Layer;
+
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<Vec<String>>>);
+
+        struct FieldVisitor(String);
+
+        impl Visit for FieldVisitor {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0.push_str(&format!("{}={:?};", field.name(), value));
+            }
+        }
+
+        impl<S: tracing::Subscriber> Layer<S> for Capture {
+            fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+                let mut visitor = FieldVisitor(String::new());
+                event.record(&mut visitor);
+                self.0.lock().unwrap().push(visitor.0);
+            }
         }
+
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let result = tracing::subscriber::with_default(subscriber, f);
+        let events = capture.0.lock().unwrap().clone();
+        (result, events)
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_trace_skipped_sleep() -> Result<()> {
+        let (result, events) = capture_events(|| smart_sleep(-5));
+        result?;
+
+        assert_eq!(events.len(), 1);
+        assert!(events[0].contains("input=Number(-5)"));
+        assert!(events[0].contains("skipping sleep"));
+        Ok(())
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_trace_other_entry_points_once() -> Result<()> {
+        use std::sync::atomic::AtomicBool;
+
+        let (result, events) = capture_events(|| crate::PreciseSleeper::new().sleep("1ms"));
+        result?;
+        assert_eq!(events.len(), 1);
+        assert!(events[0].contains("resolved=1ms"), "{}", events[0]);
+
+        let cancel = AtomicBool::new(false);
+        let (result, events) = capture_events(|| crate::smart_sleep_cancellable(0, &cancel));
+        result?;
+        assert_eq!(events.len(), 1);
+        assert!(events[0].contains("skipped=true"), "{}", events[0]);
+        Ok(())
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_trace_sleep_fields() -> Result<()> {
+        let (result, events) = capture_events(|| smart_sleep("2ms"));
+        result?;
+        let event = events
+            .iter()
+            .find(|e| e.contains("message=smart_sleep"))
+            .expect("smart_sleep event");
+        assert!(event.contains("input=Text(\"2ms\")"), "{}", event);
+        assert!(event.contains("resolved=2ms"), "{}", event);
+        assert!(event.contains("skipped=false"), "{}", event);
+
+        let (result, events) = capture_events(|| smart_sleep(0));
+        result?;
+        assert_eq!(events.len(), 1);
+        assert!(events[0].contains("resolved=0ns"), "{}", events[0]);
+        assert!(events[0].contains("skipped=true"), "{}", events[0]);
+        Ok(())
     }
 }
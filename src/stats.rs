@@ -0,0 +1,128 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::panic::Location;
+use std::sync::Mutex;
+use std::time::Duration;
+
+lazy_static! {
+    static ref SLEEP_STATS: Mutex<SleepStats> = Mutex::new(SleepStats::default());
+}
+
+/// Aggregated statistics for a single call site
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CallSiteStats {
+    /// Number of `smart_sleep` calls made from this location
+    pub calls: u64,
+    /// Total resolved sleep time requested from this location
+    pub total: Duration,
+}
+
+/// A snapshot of the sleep statistics collected by [`smart_sleep`](crate::smart_sleep).
+///
+/// Only available with the `stats` feature.
+#[derive(Debug, Clone, Default)]
+pub struct SleepStats {
+    /// Number of `smart_sleep` calls, including skipped ones
+    pub calls: u64,
+    /// Number of calls that skipped sleeping because of a zero or negative input
+    pub skipped: u64,
+    /// Total resolved sleep time across all calls
+    pub total: Duration,
+    /// Per-call-site statistics, keyed by the caller location
+    pub call_sites: HashMap<&'static Location<'static>, CallSiteStats>,
+}
+
+impl SleepStats {
+    /// Returns up to `n` call sites ordered by total sleep time, longest first.
+    pub fn top_call_sites(&self, n: usize) -> Vec<(&'static Location<'static>, CallSiteStats)> {
+        let mut sites: Vec<_> = self.call_sites.iter().map(|(l, s)| (*l, *s)).collect();
+        sites.sort_by(|a, b| b.1.total.cmp(&a.1.total).then(b.1.calls.cmp(&a.1.calls)));
+        sites.truncate(n);
+        sites
+    }
+}
+
+/// Returns a snapshot of the collected sleep statistics.
+///
+/// Only available with the `stats` feature.
+pub fn sleep_stats() -> SleepStats {
+    SLEEP_STATS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Clears all collected sleep statistics.
+///
+/// Only available with the `stats` feature.
+pub fn reset_sleep_stats() {
+    *SLEEP_STATS.lock().unwrap_or_else(|e| e.into_inner()) = SleepStats::default();
+}
+
+/// Record one `smart_sleep` call made from `location`
+pub(crate) fn record(location: &'static Location<'static>, duration: Duration) {
+    let mut stats = SLEEP_STATS.lock().unwrap_or_else(|e| e.into_inner());
+    stats.calls += 1;
+    if duration.is_zero() {
+        stats.skipped += 1;
+    }
+    stats.total = stats.total.saturating_add(duration);
+
+    let site = stats.call_sites.entry(location).or_default();
+    site.calls += 1;
+    site.total = site.total.saturating_add(duration);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sleep_frame, smart_sleep, Result};
+
+    #[test]
+    fn test_call_sites_tracked_separately() -> Result<()> {
+        let (line_a, a) = (line!(), smart_sleep("2ms"));
+        a?;
+        let mut line_b = 0;
+        for _ in 0..2 {
+            let (line, b) = (line!(), smart_sleep(1));
+            b?;
+            line_b = line;
+        }
+        // Wrappers report their caller rather than themselves
+        let (line_c, c) = (line!(), sleep_frame(1000.0));
+        c?;
+
+        let stats = sleep_stats();
+        let site = |line: u32| {
+            stats
+                .call_sites
+                .iter()
+                .find(|(l, _)| l.file() == file!() && l.line() == line)
+                .map(|(_, s)| *s)
+        };
+        assert_eq!(
+            site(line_a),
+            Some(CallSiteStats {
+                calls: 1,
+                total: Duration::from_millis(2),
+            })
+        );
+        assert_eq!(
+            site(line_b),
+            Some(CallSiteStats {
+                calls: 2,
+                total: Duration::from_millis(2),
+            })
+        );
+        assert_eq!(
+            site(line_c),
+            Some(CallSiteStats {
+                calls: 1,
+                total: Duration::from_millis(1),
+            })
+        );
+        assert!(stats.calls >= 4);
+        assert!(!stats.top_call_sites(2).is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use core::time::Duration;
+
+/// Time units for type-safe construction of sleep inputs.
+///
+/// Pairing a value with a `Unit` (see `From<(u64, Unit)>` for
+/// [`SleepInput`](crate::SleepInput)) builds a duration directly, so unlike
+/// string-based input it can never fail on an unknown unit.
+///
+/// # Examples
+///
+/// ```
+/// use sleep_utils::{SleepInput, Unit};
+/// use std::time::Duration;
+///
+/// let input = SleepInput::from((2u64, Unit::Seconds));
+/// assert_eq!(input.to_duration().unwrap(), Duration::from_secs(2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// Milliseconds
+    Millis,
+    /// Seconds
+    Seconds,
+    /// Minutes
+    Minutes,
+    /// Hours
+    Hours,
+}
+
+impl Unit {
+    /// Builds a [`Duration`] of `value` units.
+    ///
+    /// This never panics: values whose total does not fit in a [`Duration`] saturate at
+    /// `u64::MAX` seconds.
+    pub fn duration(self, value: u64) -> Duration {
+        match self {
+            Unit::Millis => Duration::from_millis(value),
+            Unit::Seconds => Duration::from_secs(value),
+            Unit::Minutes => Duration::from_secs(value.saturating_mul(60)),
+            Unit::Hours => Duration::from_secs(value.saturating_mul(3600)),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Integration tests for the Tokio-based async sleep.
+
+#![cfg(feature = "tokio")]
+
+use sleep_utils::{smart_sleep_async, Result};
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[tokio::test]
+async fn test_async_sleep_waits_for_parsed_duration() -> Result<()> {
+    let start = Instant::now();
+    smart_sleep_async("50ms").await?;
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    let start = Instant::now();
+    smart_sleep_async(Duration::from_millis(20)).await?;
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_sleep_skips_zero_and_negative() -> Result<()> {
+    let start = Instant::now();
+    smart_sleep_async(0).await?;
+    smart_sleep_async(-100).await?;
+    smart_sleep_async("0s").await?;
+    assert!(start.elapsed() < Duration::from_millis(20));
+
+    assert!(smart_sleep_async("bogus").await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_sleeps_do_not_block_the_executor() -> Result<()> {
+    // On the single-threaded test runtime, blocking sleeps would run back to back
+    let start = Instant::now();
+    let (a, b, c) = tokio::join!(
+        smart_sleep_async("100ms"),
+        smart_sleep_async("100ms"),
+        smart_sleep_async("100ms"),
+    );
+    a?;
+    b?;
+    c?;
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(100));
+    assert!(elapsed < Duration::from_millis(250), "{:?}", elapsed);
+    Ok(())
+}
@@ -0,0 +1,22 @@
+//! The initial time scale is read from the environment once, so this test needs a
+//! process of its own.
+
+use sleep_utils::{set_time_scale, smart_sleep, time_scale, Result, SCALE_ENV_VAR};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_scale_from_environment() -> Result<()> {
+    std::env::set_var(SCALE_ENV_VAR, "0.02");
+    assert_eq!(time_scale(), 0.02);
+
+    let start = Instant::now();
+    smart_sleep("1s")?;
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(20));
+    assert!(elapsed < Duration::from_millis(500));
+
+    // The programmatic scale overrides the environment
+    set_time_scale(1.0)?;
+    assert_eq!(time_scale(), 1.0);
+    Ok(())
+}
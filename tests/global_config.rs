@@ -0,0 +1,343 @@
+//! Tests for process-wide configuration.
+//!
+//! These live in their own test binary so that changing global settings cannot race
+//! with the unit tests, and every test holds [`lock`] while it touches a global.
+
+use sleep_utils::*;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Serialize access to globals and restore their defaults
+fn lock() -> MutexGuard<'static, ()> {
+    let guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    reset_all_globals();
+    guard
+}
+
+#[test]
+fn test_sleep_granularity_rounds_up() -> Result<()> {
+    let _guard = lock();
+    set_sleep_granularity(Duration::from_millis(10));
+
+    let start = Instant::now();
+    smart_sleep("13ms")?;
+    assert!(start.elapsed() >= Duration::from_millis(20));
+
+    // Zero inputs stay zero
+    let start = Instant::now();
+    smart_sleep(0)?;
+    assert!(start.elapsed() < Duration::from_millis(10));
+
+    set_sleep_granularity(Duration::ZERO);
+    Ok(())
+}
+
+#[test]
+fn test_default_unit_applies_to_unitless_input() -> Result<()> {
+    let _guard = lock();
+    set_default_unit(Unit::Seconds);
+
+    assert_eq!(parse_sleep_duration("1.5")?, Duration::from_millis(1500));
+    assert_eq!(parse_sleep_duration("2")?, Duration::from_millis(2000));
+    assert_eq!(parse_sleep_duration("0")?, Duration::ZERO);
+
+    // Inputs with explicit units are unaffected
+    assert_eq!(parse_sleep_duration("2ms")?, Duration::from_millis(2));
+
+    set_default_unit(Unit::Minutes);
+    assert_eq!(parse_sleep_duration("0.5")?, Duration::from_secs(30));
+
+    set_default_unit(Unit::Millis);
+    assert_eq!(parse_sleep_duration("2")?, Duration::from_millis(2));
+    Ok(())
+}
+
+#[test]
+fn test_time_scale_shortens_sleeps() -> Result<()> {
+    let _guard = lock();
+    set_time_scale(0.05)?;
+
+    let start = Instant::now();
+    smart_sleep("1s")?;
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(50));
+    assert!(elapsed < Duration::from_millis(500));
+
+    assert!(set_time_scale(f64::NAN).is_err());
+    assert_eq!(time_scale(), 0.05);
+
+    set_time_scale(1.0)?;
+    Ok(())
+}
+
+#[test]
+fn test_tick_units() -> Result<()> {
+    let _guard = lock();
+    assert!(parse_sleep_duration("3ticks").is_err());
+    assert!(!recognized_units().contains(&"tick"));
+
+    set_tick_duration(Some(Duration::from_millis(16)));
+    assert_eq!(parse_sleep_duration("3ticks")?, Duration::from_millis(48));
+    assert_eq!(parse_sleep_duration("1 tick")?, Duration::from_millis(16));
+    assert_eq!(parse_sleep_duration("2t")?, Duration::from_millis(32));
+    assert_eq!(
+        parse_sleep_duration("1s 2ticks")?,
+        Duration::from_millis(1032)
+    );
+    assert_eq!(parse_sleep_duration("0ticks")?, Duration::ZERO);
+    assert!(recognized_units().contains(&"tick"));
+
+    set_tick_duration(None);
+    Ok(())
+}
+
+#[test]
+fn test_zero_sleep_yields() -> Result<()> {
+    let _guard = lock();
+    assert!(!zero_sleep_yields());
+
+    set_zero_sleep_yields(true);
+    assert!(zero_sleep_yields());
+
+    // Yielding never turns a zero sleep into a real one
+    let start = Instant::now();
+    for _ in 0..100 {
+        smart_sleep(0)?;
+        smart_sleep(-5)?;
+    }
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    // Non-zero sleeps are unaffected
+    let start = Instant::now();
+    smart_sleep("5ms")?;
+    assert!(start.elapsed() >= Duration::from_millis(5));
+
+    set_zero_sleep_yields(false);
+    Ok(())
+}
+
+#[test]
+fn test_sleep_observers_all_fire() -> Result<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let _guard = lock();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let counter = Arc::clone(&calls);
+    add_sleep_observer(Box::new(move |_, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    }));
+    let recorder = Arc::clone(&seen);
+    add_sleep_observer(Box::new(move |input, duration| {
+        recorder
+            .lock()
+            .unwrap()
+            .push((format!("{:?}", input), duration));
+    }));
+
+    smart_sleep("2ms")?;
+    smart_sleep(0)?;
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            (
+                format!("{:?}", SleepInput::from("2ms")),
+                Duration::from_millis(2)
+            ),
+            (format!("{:?}", SleepInput::from(0)), Duration::ZERO),
+        ]
+    );
+
+    // Observers see the resolved, scaled duration
+    set_time_scale(0.5)?;
+    smart_sleep("10ms")?;
+    assert_eq!(seen.lock().unwrap()[2].1, Duration::from_millis(5));
+
+    clear_sleep_observers();
+    smart_sleep("1ms")?;
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    Ok(())
+}
+
+#[test]
+fn test_every_entry_point_reports_once() -> Result<()> {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let _guard = lock();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    add_sleep_observer(Box::new(move |_, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    }));
+    set_zero_sleep_yields(true);
+
+    let cancel = AtomicBool::new(false);
+    for input in ["1ms", "0"] {
+        smart_sleep(input)?;
+        PreciseSleeper::new().sleep(input)?;
+        assert_eq!(
+            smart_sleep_cancellable(input, &cancel)?,
+            SleepOutcome::Completed
+        );
+        #[cfg(feature = "tokio")]
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+            .block_on(smart_sleep_async(input))?;
+    }
+
+    let expected = if cfg!(feature = "tokio") { 8 } else { 6 };
+    assert_eq!(calls.load(Ordering::SeqCst), expected);
+    #[cfg(feature = "stats")]
+    assert_eq!(sleep_stats().calls, expected as u64);
+
+    // A flag that is already set still cancels a skipped sleep
+    let cancel = AtomicBool::new(true);
+    assert_eq!(
+        smart_sleep_cancellable(0, &cancel)?,
+        SleepOutcome::Cancelled
+    );
+    Ok(())
+}
+
+#[test]
+fn test_reset_all_globals() -> Result<()> {
+    let _guard = lock();
+    set_time_scale(0.25)?;
+    set_sleep_granularity(Duration::from_millis(10));
+    set_default_unit(Unit::Seconds);
+    set_tick_duration(Some(Duration::from_millis(16)));
+    set_zero_sleep_yields(true);
+    let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = calls.clone();
+    add_sleep_observer(Box::new(move |_, _| {
+        counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }));
+    assert_eq!(parse_sleep_duration("2")?, Duration::from_secs(2));
+
+    reset_all_globals();
+
+    assert_eq!(time_scale(), 1.0);
+    assert_eq!(sleep_granularity(), Duration::ZERO);
+    assert_eq!(default_unit(), Unit::Millis);
+    assert_eq!(tick_duration(), None);
+    assert!(!zero_sleep_yields());
+    // The parse cache must not keep the old default unit
+    assert_eq!(parse_sleep_duration("2")?, Duration::from_millis(2));
+
+    smart_sleep("1ms")?;
+    assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+    #[cfg(feature = "stats")]
+    {
+        reset_all_globals();
+        assert_eq!(sleep_stats().calls, 0);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_sleep_fraction_of_reference() -> Result<()> {
+    let _guard = lock();
+    register_reference("request_budget", Duration::from_millis(100));
+    assert_eq!(
+        reference("request_budget"),
+        Some(Duration::from_millis(100))
+    );
+
+    let start = Instant::now();
+    let slept = sleep_fraction_of("request_budget", 0.5)?;
+    assert_eq!(slept, Duration::from_millis(50));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    assert!(matches!(
+        sleep_fraction_of("request_budget", -0.5),
+        Err(SleepError::NumberOutOfRange(_))
+    ));
+    assert!(matches!(
+        sleep_fraction_of("unknown", 0.5),
+        Err(SleepError::InvalidDuration(_))
+    ));
+
+    reset_all_globals();
+    assert_eq!(reference("request_budget"), None);
+    Ok(())
+}
+
+#[test]
+fn test_max_sleep_limit() -> Result<()> {
+    let _guard = lock();
+    set_max_sleep(Some(Duration::from_secs(1)));
+    assert_eq!(max_sleep(), Some(Duration::from_secs(1)));
+
+    let start = Instant::now();
+    match smart_sleep("5s") {
+        Err(SleepError::NumberOutOfRange(message)) => {
+            assert!(
+                message.contains("5s") && message.contains("1s"),
+                "{}",
+                message
+            )
+        }
+        other => panic!("expected NumberOutOfRange, got {:?}", other),
+    }
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    smart_sleep("500ms")?;
+
+    set_time_scale(0.01)?;
+    // The limit applies after scaling, so a scaled-down long request fits
+    smart_sleep("5s")?;
+
+    set_max_sleep(None);
+    assert_eq!(max_sleep(), None);
+    Ok(())
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_retry_sleeps_through_smart_sleep() -> Result<()> {
+    let _guard = lock();
+    let config = RetryConfig::new("1s")?.with_max_attempts(2)?;
+
+    // The time scale shortens the backoff delay like any other smart_sleep
+    set_time_scale(0.01)?;
+    let mut calls = 0;
+    let start = Instant::now();
+    let result = retry(
+        || {
+            calls += 1;
+            if calls < 2 {
+                Err("not yet")
+            } else {
+                Ok(calls)
+            }
+        },
+        config.clone(),
+    );
+    assert_eq!(result, Ok(2));
+    assert!(start.elapsed() < Duration::from_millis(500));
+
+    // A delay above the sleep limit ends the retries instead of being slept
+    set_time_scale(1.0)?;
+    set_max_sleep(Some(Duration::from_millis(100)));
+    let start = Instant::now();
+    let mut calls = 0;
+    let failing = retry(
+        || {
+            calls += 1;
+            Err::<(), _>(calls)
+        },
+        config,
+    );
+    assert_eq!(failing, Err(1));
+    assert!(start.elapsed() < Duration::from_millis(500));
+    Ok(())
+}